@@ -0,0 +1,271 @@
+// Copyright 2017 LambdaStack All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Library Documentation
+//!
+//! Request bodies streamed from a `Read` source instead of a byte slice held in memory.
+//!
+//! With V4 signatures the body is sent as `aws-chunked` (`STREAMING-AWS4-HMAC-SHA256-PAYLOAD`)
+//! where every chunk is signed as it is read so the payload is never hashed up front. With V2
+//! signatures the body is sent as is.
+//!
+//! ```ignore
+//! let file = try!(File::open("large.iso"));
+//! let length = try!(file.metadata()).len();
+//!
+//! let mut request = PutObjectRequest::default();
+//! request.bucket = "bucket".to_string();
+//! request.key = "large.iso".to_string();
+//! request.body_stream = Some(StreamingBody::new(file, length));
+//! ```
+//!
+//! NB: A stream can only be read once so requests with a `StreamingBody` are not retried and
+//! redirects are not followed.
+//!
+//! Response bodies can be written to a `Write` sink as they are read (see
+//! `S3Client::get_object_to_writer`). The `BodySink` counts the bytes so a truncated body is
+//! detected and a request that already wrote part of a body is not retried.
+
+use std::cell::{RefCell, RefMut};
+use std::cmp;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use rustc_serialize::{Encodable, Encoder};
+
+use aws::common::signature::ChunkSigner;
+
+/// Size of the chunks of an `aws-chunked` upload (the minimum S3 allows is 8KB).
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A request body read from a `Read` source of a known length. The source must be `Send` so
+/// requests holding a body can be moved to another thread.
+pub struct StreamingBody {
+    reader: RefCell<Box<Read + Send>>,
+    length: u64,
+}
+
+// Reads exactly `remaining` bytes from the inner reader. The Content-Length is sent before the
+// body so a source that ends early is an error instead of a short body.
+struct LengthReader<R> {
+    inner: R,
+    remaining: u64,
+}
+
+/// Encodes a body read from a `Read` source as `aws-chunked`. Each chunk is
+/// `hex(size);chunk-signature=signature\r\ndata\r\n` and the body ends with an empty chunk.
+pub struct AwsChunkedReader<'a> {
+    inner: &'a mut Read,
+    signer: ChunkSigner,
+    buffer: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+/// A response body sink that counts the bytes written to the inner `Write`.
+pub struct BodySink<'a> {
+    inner: &'a mut Write,
+    written: u64,
+}
+
+// Impls below...
+
+impl StreamingBody {
+    /// Creates a body that reads `length` bytes from `reader`. Reading fails with
+    /// `UnexpectedEof` if `reader` ends before `length` bytes.
+    pub fn new<R: Read + Send + 'static>(reader: R, length: u64) -> StreamingBody {
+        StreamingBody {
+            reader: RefCell::new(Box::new(LengthReader { inner: reader, remaining: length })),
+            length: length,
+        }
+    }
+
+    /// Returns the length of the body in bytes.
+    pub fn len(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns true if the body is empty.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the reader of the body. Panics if it is already borrowed.
+    pub fn reader(&self) -> RefMut<Box<Read + Send>> {
+        self.reader.borrow_mut()
+    }
+}
+
+impl fmt::Debug for StreamingBody {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "StreamingBody {{ length: {} }}", self.length)
+    }
+}
+
+// NOTE: Only the length is encoded since the content is a stream.
+impl Encodable for StreamingBody {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        s.emit_u64(self.length)
+    }
+}
+
+impl<R: Read> Read for LengthReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let max = cmp::min(buf.len() as u64, self.remaining) as usize;
+        if max == 0 {
+            return Ok(0);
+        }
+
+        let read = try!(self.inner.read(&mut buf[..max]));
+        if read == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                      format!("Body ended {} bytes before its length", self.remaining)));
+        }
+        self.remaining -= read as u64;
+        Ok(read)
+    }
+}
+
+impl<'a> AwsChunkedReader<'a> {
+    pub fn new(inner: &'a mut Read, signer: ChunkSigner) -> AwsChunkedReader<'a> {
+        AwsChunkedReader {
+            inner: inner,
+            signer: signer,
+            buffer: Vec::new(),
+            position: 0,
+            finished: false,
+        }
+    }
+
+    // Reads the next chunk of the inner reader and encodes it in the buffer.
+    fn next_chunk(&mut self) -> io::Result<()> {
+        let mut data = vec![0u8; CHUNK_SIZE];
+        let mut size = 0;
+        while size < CHUNK_SIZE {
+            let read = try!(self.inner.read(&mut data[size..]));
+            if read == 0 {
+                break;
+            }
+            size += read;
+        }
+        data.truncate(size);
+
+        let signature = self.signer.sign_chunk(&data);
+        self.buffer = format!("{:x};chunk-signature={}\r\n", size, signature).into_bytes();
+        self.buffer.extend_from_slice(&data);
+        self.buffer.extend_from_slice(b"\r\n");
+        self.position = 0;
+        self.finished = size == 0;
+        Ok(())
+    }
+}
+
+impl<'a> Read for AwsChunkedReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.buffer.len() {
+            if self.finished {
+                return Ok(0);
+            }
+            try!(self.next_chunk());
+        }
+
+        let size = cmp::min(buf.len(), self.buffer.len() - self.position);
+        buf[..size].copy_from_slice(&self.buffer[self.position..self.position + size]);
+        self.position += size;
+        Ok(size)
+    }
+}
+
+impl<'a> BodySink<'a> {
+    pub fn new(inner: &'a mut Write) -> BodySink<'a> {
+        BodySink {
+            inner: inner,
+            written: 0,
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+}
+
+impl<'a> Write for BodySink<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = try!(self.inner.write(buf));
+        self.written += size as u64;
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Returns the Content-Length of an `aws-chunked` body with `length` bytes of data.
+pub fn aws_chunked_length(length: u64) -> u64 {
+    let chunk_size = CHUNK_SIZE as u64;
+    // hex(size) + ";chunk-signature=" + 64 hex digit signature + "\r\n" + data + "\r\n"
+    let encoded = |size: u64| format!("{:x}", size).len() as u64 + 17 + 64 + 2 + size + 2;
+
+    let full_chunks = length / chunk_size;
+    let remainder = length % chunk_size;
+    let mut total = full_chunks * encoded(chunk_size) + encoded(0);
+    if remainder > 0 {
+        total += encoded(remainder);
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{Cursor, Read};
+
+    use aws::common::signature::ChunkSigner;
+
+    #[test]
+    fn aws_chunked_encoding() {
+        let data = vec![b'a'; CHUNK_SIZE + 10];
+        let body = StreamingBody::new(Cursor::new(data), (CHUNK_SIZE + 10) as u64);
+        let signer = ChunkSigner::new(vec![1u8; 32], "20170101T000000Z", "20170101/us-east-1/s3/aws4_request", "seed");
+
+        let mut encoded = Vec::new();
+        {
+            let mut reader = body.reader();
+            AwsChunkedReader::new(&mut *reader, signer).read_to_end(&mut encoded).unwrap();
+        }
+
+        assert_eq!(encoded.len() as u64, aws_chunked_length(body.len()));
+        assert!(encoded.starts_with(b"10000;chunk-signature="));
+        assert!(encoded.ends_with(b"\r\n\r\n"));
+    }
+
+    #[test]
+    fn short_body_is_an_error() {
+        let body = StreamingBody::new(Cursor::new(vec![b'a'; 10]), 20);
+        let signer = ChunkSigner::new(vec![1u8; 32], "20170101T000000Z", "20170101/us-east-1/s3/aws4_request", "seed");
+
+        let mut encoded = Vec::new();
+        let mut reader = body.reader();
+        let err = AwsChunkedReader::new(&mut *reader, signer).read_to_end(&mut encoded).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let body = StreamingBody::new(Cursor::new(vec![b'a'; 10]), 20);
+        let mut data = Vec::new();
+        let err = body.reader().read_to_end(&mut data).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
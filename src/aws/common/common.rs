@@ -210,6 +210,8 @@ pub struct Operation {
     pub duration: Option<Duration>,
     /// Object Name
     pub object: String,
+    /// Number of times the request was retried after the first attempt
+    pub retries: u32,
 }
 
 // Impls below...
@@ -29,6 +29,8 @@ pub mod xmlutil;
 pub mod signature;
 /// `params` contains the type, struct, enum and impls that related to URI parameters.
 pub mod params;
+/// `body` contains the request bodies streamed from a `Read` source.
+pub mod body;
 /// `request` contains the type, struct, enum and impls that are HTTP Request related.
 pub mod request;
 /// `macros` contains the macros defined for the library.
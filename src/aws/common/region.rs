@@ -28,20 +28,49 @@ use std::fmt::{Display, Error as FmtError, Formatter};
 
 /// An AWS region.
 /// CnNorth1 is currently untested due to Rusoto maintainers not having access to AWS China.
-#[derive(Debug, Clone, Copy, PartialEq, RustcDecodable, RustcEncodable)]
+///
+/// `Custom` is for third party services such as Ceph RGW where the region is the name of a
+/// zonegroup (e.g. "default" or "us-lab") and `endpoint` is the hostname (or URL) of the service
+/// in that zonegroup.
+#[derive(Debug, Clone, PartialEq, RustcDecodable, RustcEncodable)]
 pub enum Region {
+    AfSouth1,
+    ApEast1,
     ApNortheast1,
     ApNortheast2,
+    ApNortheast3,
     ApSouth1,
+    ApSouth2,
     ApSoutheast1,
     ApSoutheast2,
+    ApSoutheast3,
+    ApSoutheast4,
+    CaCentral1,
+    CaWest1,
     EuCentral1,
+    EuCentral2,
+    EuNorth1,
+    EuSouth1,
+    EuSouth2,
     EuWest1,
+    EuWest2,
+    EuWest3,
+    IlCentral1,
+    MeCentral1,
+    MeSouth1,
     SaEast1,
     UsEast1,
+    UsEast2,
     UsWest1,
     UsWest2,
+    UsGovEast1,
+    UsGovWest1,
     CnNorth1,
+    CnNorthwest1,
+    Custom {
+        name: String,
+        endpoint: String,
+    },
 }
 
 /// An error produced when attempting to convert a `str` into a `Region` fails.
@@ -50,44 +79,128 @@ pub struct ParseRegionError {
     message: String,
 }
 
-impl Display for Region {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), FmtError> {
-        let region_str = match *self {
+impl Region {
+    /// Creates a `Custom` region for a third party service. `name` is used in V4 signatures and
+    /// `endpoint` is the hostname (or URL) of the service, e.g.
+    /// `Region::custom("default", "https://rgw.example.com:8080")`.
+    pub fn custom<N: Into<String>, E: Into<String>>(name: N, endpoint: E) -> Region {
+        Region::Custom {
+            name: name.into(),
+            endpoint: endpoint.into(),
+        }
+    }
+
+    /// Returns the name of the region as used in V4 signatures and location constraints.
+    pub fn name(&self) -> &str {
+        match *self {
+            Region::AfSouth1 => "af-south-1",
+            Region::ApEast1 => "ap-east-1",
             Region::ApNortheast1 => "ap-northeast-1",
             Region::ApNortheast2 => "ap-northeast-2",
+            Region::ApNortheast3 => "ap-northeast-3",
             Region::ApSouth1 => "ap-south-1",
+            Region::ApSouth2 => "ap-south-2",
             Region::ApSoutheast1 => "ap-southeast-1",
             Region::ApSoutheast2 => "ap-southeast-2",
+            Region::ApSoutheast3 => "ap-southeast-3",
+            Region::ApSoutheast4 => "ap-southeast-4",
+            Region::CaCentral1 => "ca-central-1",
+            Region::CaWest1 => "ca-west-1",
             Region::EuCentral1 => "eu-central-1",
+            Region::EuCentral2 => "eu-central-2",
+            Region::EuNorth1 => "eu-north-1",
+            Region::EuSouth1 => "eu-south-1",
+            Region::EuSouth2 => "eu-south-2",
             Region::EuWest1 => "eu-west-1",
+            Region::EuWest2 => "eu-west-2",
+            Region::EuWest3 => "eu-west-3",
+            Region::IlCentral1 => "il-central-1",
+            Region::MeCentral1 => "me-central-1",
+            Region::MeSouth1 => "me-south-1",
             Region::SaEast1 => "sa-east-1",
             Region::UsEast1 => "us-east-1",
+            Region::UsEast2 => "us-east-2",
             Region::UsWest1 => "us-west-1",
             Region::UsWest2 => "us-west-2",
+            Region::UsGovEast1 => "us-gov-east-1",
+            Region::UsGovWest1 => "us-gov-west-1",
             Region::CnNorth1 => "cn-north-1",
-        };
+            Region::CnNorthwest1 => "cn-northwest-1",
+            Region::Custom { ref name, .. } => name,
+        }
+    }
+
+    /// Returns the hostname of the endpoint of a `Custom` region (without the scheme or port).
+    /// `None` if the region has an empty endpoint.
+    pub fn custom_hostname(&self) -> Option<String> {
+        match *self {
+            Region::Custom { ref endpoint, .. } if !endpoint.is_empty() => {
+                let endpoint = match endpoint.find("://") {
+                    Some(index) => &endpoint[index + 3..],
+                    None => &endpoint[..],
+                };
+                let endpoint = endpoint.split('/').next().unwrap_or("");
+                Some(endpoint.split(':').next().unwrap_or("").to_string())
+            },
+            _ => None,
+        }
+    }
 
-        write!(f, "{}", region_str)
+    /// Returns true for the regions of the China partition (amazonaws.com.cn).
+    pub fn is_china(&self) -> bool {
+        match *self {
+            Region::CnNorth1 | Region::CnNorthwest1 => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FmtError> {
+        write!(f, "{}", self.name())
     }
 }
 
+/// Parses the AWS region names. `Custom` regions can't be parsed since they need an endpoint; use
+/// `Region::custom` instead.
 impl FromStr for Region {
     type Err = ParseRegionError;
 
     fn from_str(s: &str) -> Result<Region, ParseRegionError> {
         match s {
+            "af-south-1" => Ok(Region::AfSouth1),
+            "ap-east-1" => Ok(Region::ApEast1),
             "ap-northeast-1" => Ok(Region::ApNortheast1),
             "ap-northeast-2" => Ok(Region::ApNortheast2),
+            "ap-northeast-3" => Ok(Region::ApNortheast3),
             "ap-south-1" => Ok(Region::ApSouth1),
+            "ap-south-2" => Ok(Region::ApSouth2),
             "ap-southeast-1" => Ok(Region::ApSoutheast1),
             "ap-southeast-2" => Ok(Region::ApSoutheast2),
+            "ap-southeast-3" => Ok(Region::ApSoutheast3),
+            "ap-southeast-4" => Ok(Region::ApSoutheast4),
+            "ca-central-1" => Ok(Region::CaCentral1),
+            "ca-west-1" => Ok(Region::CaWest1),
             "eu-central-1" => Ok(Region::EuCentral1),
+            "eu-central-2" => Ok(Region::EuCentral2),
+            "eu-north-1" => Ok(Region::EuNorth1),
+            "eu-south-1" => Ok(Region::EuSouth1),
+            "eu-south-2" => Ok(Region::EuSouth2),
             "eu-west-1" => Ok(Region::EuWest1),
+            "eu-west-2" => Ok(Region::EuWest2),
+            "eu-west-3" => Ok(Region::EuWest3),
+            "il-central-1" => Ok(Region::IlCentral1),
+            "me-central-1" => Ok(Region::MeCentral1),
+            "me-south-1" => Ok(Region::MeSouth1),
             "sa-east-1" => Ok(Region::SaEast1),
             "us-east-1" => Ok(Region::UsEast1),
+            "us-east-2" => Ok(Region::UsEast2),
             "us-west-1" => Ok(Region::UsWest1),
             "us-west-2" => Ok(Region::UsWest2),
+            "us-gov-east-1" => Ok(Region::UsGovEast1),
+            "us-gov-west-1" => Ok(Region::UsGovWest1),
             "cn-north-1" => Ok(Region::CnNorth1),
+            "cn-northwest-1" => Ok(Region::CnNorthwest1),
             s => Err(ParseRegionError::new(s)),
         }
     }
@@ -135,6 +248,10 @@ mod tests {
         assert_eq!("us-west-1".parse(), Ok(Region::UsWest1));
         assert_eq!("us-west-2".parse(), Ok(Region::UsWest2));
         assert_eq!("cn-north-1".parse(), Ok(Region::CnNorth1));
+        assert_eq!("ca-central-1".parse(), Ok(Region::CaCentral1));
+        assert_eq!("eu-north-1".parse(), Ok(Region::EuNorth1));
+        assert_eq!("us-gov-west-1".parse(), Ok(Region::UsGovWest1));
+        assert_eq!("cn-northwest-1".parse(), Ok(Region::CnNorthwest1));
     }
 
     #[test]
@@ -155,5 +272,20 @@ mod tests {
         assert_eq!(Region::UsWest1.to_string(), "us-west-1".to_owned());
         assert_eq!(Region::UsWest2.to_string(), "us-west-2".to_owned());
         assert_eq!(Region::CnNorth1.to_string(), "cn-north-1".to_owned());
+        assert_eq!(Region::MeSouth1.to_string(), "me-south-1".to_owned());
+
+        let custom = Region::Custom { name: "us-lab".to_string(), endpoint: "https://rgw.lab.example.com:8080".to_string() };
+        assert_eq!(custom.to_string(), "us-lab".to_owned());
+        assert_eq!(custom.custom_hostname(), Some("rgw.lab.example.com".to_string()));
+    }
+
+    #[test]
+    fn custom_region() {
+        let region = Region::custom("default", "rgw.example.com");
+        assert_eq!(region, Region::Custom { name: "default".to_string(), endpoint: "rgw.example.com".to_string() });
+        assert_eq!(region.to_string(), "default".to_owned());
+        assert_eq!(region.custom_hostname(), Some("rgw.example.com".to_string()));
+        assert_eq!(Region::custom("default", "").custom_hostname(), None);
+        assert_eq!(Region::EuWest2.to_string().parse(), Ok(Region::EuWest2));
     }
 }
@@ -20,17 +20,22 @@
 //!
 //! Wraps the Hyper library to send PUT, POST, DELETE and GET requests.
 
-use std::io::Read;
-use std::io::Error as IoError;
+use std::borrow::Cow;
+use std::io::{self, Read, Write};
+use std::io::{Error as IoError, ErrorKind};
+use std::time::Instant;
 use std::error::Error;
 use std::fmt;
 use std::collections::HashMap;
 
 use hyper::Client;
+use hyper::client::{Body as HyperBody, Response};
 use hyper::Error as HyperError;
 use hyper::header::Headers;
 use hyper::method::Method;
 
+use aws::common::body::{AwsChunkedReader, aws_chunked_length};
+use aws::common::common::Body;
 use aws::common::signature::SignedRequest;
 
 /// Wraps the Hyper Response that comes back from AWS S3.
@@ -41,11 +46,8 @@ use aws::common::signature::SignedRequest;
 pub struct HttpResponse {
     /// HTTP status code
     pub status: u16,
-    /// XML payload
-    pub body: String,
-    pub body_buffer: Vec<u8>, //&'a[u8],
-    // used by the get_body function to return the correct slice...
-    pub is_body: bool,
+    /// Payload as it was received. XML for API responses and the object data for get_object.
+    pub body: Body,
     /// Unsorted list of header attributes
     pub headers: HashMap<String, String>,
 }
@@ -54,6 +56,22 @@ pub struct HttpResponse {
 #[derive(Debug, Default, PartialEq, RustcDecodable, RustcEncodable)]
 pub struct HttpDispatchError {
     message: String,
+    timeout: bool,
+}
+
+impl HttpResponse {
+    /// Returns the body as text. Invalid UTF-8 sequences are replaced with U+FFFD so only the
+    /// body of a binary object is copied.
+    pub fn body_str(&self) -> Cow<str> {
+        String::from_utf8_lossy(&self.body)
+    }
+}
+
+impl HttpDispatchError {
+    /// Returns true if the request failed because a timeout of the `ConnectionOptions` expired.
+    pub fn is_timeout(&self) -> bool {
+        self.timeout
+    }
 }
 
 impl Error for HttpDispatchError {
@@ -70,110 +88,233 @@ impl fmt::Display for HttpDispatchError {
 
 impl From<HyperError> for HttpDispatchError {
     fn from(err: HyperError) -> HttpDispatchError {
-        HttpDispatchError { message: err.description().to_string() }
+        let timeout = match err {
+            HyperError::Io(ref err) => is_timeout(err),
+            _ => false,
+        };
+        HttpDispatchError { message: err.description().to_string(), timeout: timeout }
     }
 }
 
 impl From<IoError> for HttpDispatchError {
     fn from(err: IoError) -> HttpDispatchError {
-        HttpDispatchError { message: err.description().to_string() }
+        HttpDispatchError { message: err.description().to_string(), timeout: is_timeout(&err) }
     }
 }
 
+// NB: Socket read and write timeouts are WouldBlock on Unix and TimedOut on Windows.
+fn is_timeout(err: &IoError) -> bool {
+    err.kind() == ErrorKind::TimedOut || err.kind() == ErrorKind::WouldBlock
+}
+
 /// Trait that is added to the Hyper Client type. This is where ALL HTTP calls get sent from.
 pub trait DispatchSignedRequest {
     fn dispatch(&self, request: &SignedRequest) -> Result<HttpResponse, HttpDispatchError>;
+
+    /// Sends the request and writes the body of a successful (2xx) response to `writer` instead
+    /// of returning it in the `HttpResponse`. Error responses are returned with their body so
+    /// the error XML can be parsed.
+    ///
+    /// The default implementation writes the body of `dispatch` so dispatchers that only
+    /// implement `dispatch` still work but hold the whole body in memory.
+    fn dispatch_to_writer(&self,
+                          request: &SignedRequest,
+                          writer: &mut Write)
+                          -> Result<HttpResponse, HttpDispatchError> {
+        let mut response = try!(self.dispatch(request));
+        if response.status >= 200 && response.status < 300 {
+            try!(writer.write_all(&response.body));
+            response.body = Body::new();
+        }
+        Ok(response)
+    }
 }
 
 impl DispatchSignedRequest for Client {
     fn dispatch(&self,
                 request: &SignedRequest
             ) -> Result<HttpResponse, HttpDispatchError> {
-        let hyper_method = match request.method().as_ref() {
-            "POST" => Method::Post,
-            "PUT" => Method::Put,
-            "DELETE" => Method::Delete,
-            "GET" => Method::Get,
-            "HEAD" => Method::Head,
-            v @ _ => return Err(HttpDispatchError { message: format!("Unsupported HTTP verb {}", v) }),
-        };
+        let deadline = request_deadline(request);
+        let hyper_response = try!(send(self, request, deadline));
+        read_response(hyper_response, deadline)
+    }
 
-        // translate the headers map to a format Hyper likes
-        let mut hyper_headers = Headers::new();
-        for h in request.headers().iter() {
-            hyper_headers.set_raw(h.0.to_owned(), h.1.to_owned());
+    // NB: The body is copied from the socket to the writer with a small buffer so large objects
+    // are never held in memory.
+    fn dispatch_to_writer(&self,
+                          request: &SignedRequest,
+                          writer: &mut Write)
+                          -> Result<HttpResponse, HttpDispatchError> {
+        let deadline = request_deadline(request);
+        let hyper_response = try!(send(self, request, deadline));
+        let status = hyper_response.status.to_u16();
+        if status < 200 || status >= 300 {
+            return read_response(hyper_response, deadline);
         }
 
-        let epp = request.endpoint().clone().endpoint.unwrap().port();
-        let port_str = match epp {
-            Some(port) => format!(":{}", port),
-            _ => "".to_string(),
-        };
+        let headers = response_headers(&hyper_response);
+        try!(io::copy(&mut DeadlineReader::new(hyper_response, deadline), writer));
 
-        let mut final_uri = format!("{}://{}{}{}",
-                                    request.endpoint_scheme(),
-                                    request.hostname(),
-                                    port_str,
-                                    request.path());
-        if !request.canonical_query_string().is_empty() {
-            let uri = final_uri.clone();
-            final_uri = final_uri + &format!("{}{}", if uri.contains("?") {""} else {"?"}, request.canonical_query_string());
-            final_uri = final_uri.replace("?", &request.path_options().unwrap_or("?".to_string()));
-        } else {
-            final_uri = final_uri + &format!("{}", request.path_options().unwrap_or("".to_string()));
-        }
+        Ok(HttpResponse {
+            status: status,
+            body: Body::new(),
+            headers: headers,
+        })
+    }
+}
 
-        // SENDS
-        let mut hyper_response = match request.payload() {
-            None => try!(self.request(hyper_method, &final_uri).headers(hyper_headers).body("").send()),
-            Some(payload_contents) => try!(self.request(hyper_method, &final_uri)
-                                                            .headers(hyper_headers)
-                                                            .body(payload_contents)
-                                                            .send()),
-        };
+// Sends the request and returns the response with the body still unread.
+fn send(client: &Client, request: &SignedRequest, deadline: Option<Instant>) -> Result<Response, HttpDispatchError> {
+    let hyper_method = match request.method().as_ref() {
+        "POST" => Method::Post,
+        "PUT" => Method::Put,
+        "DELETE" => Method::Delete,
+        "GET" => Method::Get,
+        "HEAD" => Method::Head,
+        v @ _ => return Err(HttpDispatchError { message: format!("Unsupported HTTP verb {}", v), timeout: false }),
+    };
 
-        let mut headers: HashMap<String, String> = HashMap::new();
-        for header in hyper_response.headers.iter() {
-            headers.insert(header.name().to_string(), header.value_string());
-        }
+    // translate the headers map to a format Hyper likes
+    let mut hyper_headers = Headers::new();
+    for h in request.headers().iter() {
+        hyper_headers.set_raw(h.0.to_owned(), h.1.to_owned());
+    }
+
+    let final_uri = try!(request_uri(request));
+
+    // SENDS
+    let hyper_response = match (request.payload_stream(), request.payload()) {
+        // Streamed payloads are read as they are sent (aws-chunked with V4)
+        (Some(stream), _) => {
+            let mut stream_reader = stream.reader();
+            let mut reader = DeadlineReader::new(&mut *stream_reader, deadline);
+            match request.chunk_signer() {
+                Some(signer) => {
+                    let mut chunked = AwsChunkedReader::new(&mut reader, signer.clone());
+                    try!(client.request(hyper_method, &final_uri)
+                             .headers(hyper_headers)
+                             .body(HyperBody::SizedBody(&mut chunked, aws_chunked_length(stream.len())))
+                             .send())
+                },
+                None => try!(client.request(hyper_method, &final_uri)
+                                 .headers(hyper_headers)
+                                 .body(HyperBody::SizedBody(&mut reader, stream.len()))
+                                 .send()),
+            }
+        },
+        (None, None) => try!(client.request(hyper_method, &final_uri).headers(hyper_headers).body("").send()),
+        (None, Some(payload_contents)) => try!(client.request(hyper_method, &final_uri)
+                                                        .headers(hyper_headers)
+                                                        .body(payload_contents)
+                                                        .send()),
+    };
 
-        // The initial way was to string but UTF8 errors occured on certain object types so switched
-        let mut is_body = true;
+    Ok(hyper_response)
+}
+
+/// Returns the URI the request is sent to. The query string is appended to the path with '&'
+/// if the path already has a query (e.g. the sub-resource of a V2 request).
+pub fn request_uri(request: &SignedRequest) -> Result<String, HttpDispatchError> {
+    let epp = match request.endpoint().endpoint {
+        Some(ref url) => url.port(),
+        None => return Err(HttpDispatchError { message: "Endpoint has no URL".to_string(), timeout: false }),
+    };
+    let port_str = match epp {
+        Some(port) => format!(":{}", port),
+        _ => "".to_string(),
+    };
+
+    let mut final_uri = format!("{}://{}{}{}",
+                                request.endpoint_scheme(),
+                                request.hostname(),
+                                port_str,
+                                request.path());
+    if !request.canonical_query_string().is_empty() {
+        let uri = final_uri.clone();
+        final_uri = final_uri + &format!("{}{}", if uri.contains("?") {"&"} else {"?"}, request.canonical_query_string());
+        final_uri = final_uri.replace("?", &request.path_options().unwrap_or("?".to_string()));
+    } else {
+        final_uri = final_uri + &format!("{}", request.path_options().unwrap_or("".to_string()));
+    }
+
+    Ok(final_uri)
+}
+
+// Translates the hyper headers to the map of the HttpResponse.
+fn response_headers(response: &Response) -> HashMap<String, String> {
+    let mut headers: HashMap<String, String> = HashMap::new();
+    for header in response.headers.iter() {
+        headers.insert(header.name().to_string(), header.value_string());
+    }
+    headers
+}
+
+// Reads the whole body of a response. Used for the XML of API responses and errors.
+fn read_response(hyper_response: Response, deadline: Option<Instant>) -> Result<HttpResponse, HttpDispatchError> {
+    let headers = response_headers(&hyper_response);
+    let status = hyper_response.status.to_u16();
+
+    let mut body = Body::new();
+    // A connection dropped while reading the body is a dispatch error and not an empty body.
+    try!(DeadlineReader::new(hyper_response, deadline).read_to_end(&mut body));
+
+    Ok(HttpResponse {
+        status: status,
+        body: body,
+        headers: headers,
+    })
+}
+
+// Time by which the request_timeout of the endpoint expires.
+fn request_deadline(request: &SignedRequest) -> Option<Instant> {
+    request.endpoint().connection.request_timeout.map(|timeout| Instant::now() + timeout)
+}
 
-        // NB: Should be a better way to do this for compressed or binary files but works.
-        let mut buffer: Vec<u8> = Vec::new();
-        let body:String;
-        let size: usize;
+// Fails with a TimedOut error once the deadline has passed. Connecting and waiting for the
+// response headers are bounded by the connect and read timeouts so the deadline is checked while
+// a streamed request body is sent and while the response body is read.
+struct DeadlineReader<R> {
+    inner: R,
+    deadline: Option<Instant>,
+}
 
-        match hyper_response.read_to_end(&mut buffer) {
-            Ok(len) => {size = len},
-            _ => {size = 0 as usize},
+impl<R: Read> DeadlineReader<R> {
+    fn new(inner: R, deadline: Option<Instant>) -> DeadlineReader<R> {
+        DeadlineReader {
+            inner: inner,
+            deadline: deadline,
         }
+    }
+}
 
-        if size > 0 {
-            match String::from_utf8(buffer.clone()) {
-                Ok(buf) => {
-                    body = buf;
-                    buffer = Vec::new();
-                },
-                _ => {
-                    body = String::new();
-                    is_body = false;
-                },
+impl<R: Read> Read for DeadlineReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err(IoError::new(ErrorKind::TimedOut, "Request timed out"));
             }
-        } else {
-            body = String::new();
         }
+        self.inner.read(buf)
+    }
+}
 
-        // The HttpResponse also contains a body_buffer for large binaries mainly. Body is used
-        // most often. Typically, on get_object cares.
-        // Some bodies are XML and some are binary. Body is for String and body_buffer is for binary.
-        Ok(HttpResponse {
-            status: hyper_response.status.to_u16(),
-            body: body,
-            body_buffer: buffer,
-            is_body: is_body,
-            headers: headers,
-        })
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    #[test]
+    fn expired_deadline_times_out() {
+        let mut body = Vec::new();
+        let err = DeadlineReader::new(Cursor::new(vec![1u8; 16]), Some(Instant::now()))
+            .read_to_end(&mut body)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+        assert!(body.is_empty());
+        assert!(HttpDispatchError::from(err).is_timeout());
+
+        DeadlineReader::new(Cursor::new(vec![1u8; 16]), None).read_to_end(&mut body).unwrap();
+        assert_eq!(body.len(), 16);
     }
 }
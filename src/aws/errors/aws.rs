@@ -50,6 +50,9 @@ pub struct AWSError {
     pub missing_header_name: String,
     /// expanded_message contains the details of a given error message from AWS.
     pub expanded_message: String,
+    /// region is the region the bucket is in when the request was sent to the wrong region
+    /// (AuthorizationHeaderMalformed).
+    pub region: String,
 }
 
 impl fmt::Display for AWSError {
@@ -107,6 +110,10 @@ impl AWSError {
                 obj.missing_header_name = try!(MissingHeaderNameParser::parse_xml("MissingHeaderName", stack));
                 continue;
             }
+            if current_name == "Region" {
+                obj.region = try!(string_field("Region", stack));
+                continue;
+            }
             if current_name == tag_name || current_name.len() == 0 {
                 break;
             }
@@ -25,6 +25,7 @@ use std::fmt;
 
 use aws::errors::aws::*;
 use aws::errors::creds::CredentialsError;
+use aws::common::request::HttpDispatchError;
 use aws::common::xmlutil::*;
 use aws::common::params::*;
 use aws::common::common::*;
@@ -33,13 +34,51 @@ use aws::s3::object::*;
 
 /// S3Error is a larger container error struct that contains a more general error about the task
 /// that was requested + `AWSError` which contains the details from AWS S3.
+///
+/// `kind` classifies the error so callers can match on it instead of comparing `aws.code`
+/// strings. `status` is the HTTP status of the response or 0 if the request never got one.
 //#[derive(Debug)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct S3Error {
+    pub kind: S3ErrorKind,
+    pub status: u16,
     pub message: String,
     pub aws: AWSError
 }
 
+/// S3ErrorKind - The type of an `S3Error`.
+///
+/// Errors returned by S3 are mapped from the `Code` element of the error XML (or from the HTTP
+/// status when the response has no body such as HEAD). Codes that don't have a variant are kept
+/// in `Unknown`. Errors that happen before or after the service responds (transport, timeouts,
+/// credentials and response parsing) have their own variants.
+#[derive(Debug, Clone, PartialEq, RustcDecodable, RustcEncodable)]
+pub enum S3ErrorKind {
+    NoSuchKey,
+    NoSuchBucket,
+    AccessDenied,
+    BucketAlreadyExists,
+    BucketAlreadyOwnedByYou,
+    InvalidRange,
+    NotModified,
+    PreconditionFailed,
+    SlowDown,
+    RequestTimeTooSkewed,
+    SignatureDoesNotMatch,
+    /// The request could not be sent or the response could not be read.
+    Transport,
+    /// A timeout of the `ConnectionOptions` of the endpoint expired.
+    Timeout,
+    /// Credentials could not be found or loaded.
+    Credentials,
+    /// The XML of the response could not be parsed.
+    XmlParse,
+    /// A response header could not be parsed.
+    HeaderParse,
+    /// Any other error code (empty if there is no code).
+    Unknown(String),
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct S3ClientError {
@@ -58,15 +97,69 @@ pub struct S3ClientErrorWriter;
 
 // Impls below...
 
+impl Default for S3ErrorKind {
+    fn default() -> S3ErrorKind {
+        S3ErrorKind::Unknown(String::new())
+    }
+}
+
+impl S3ErrorKind {
+    /// Maps the `Code` of an AWS error to its kind.
+    pub fn from_code(code: &str) -> S3ErrorKind {
+        match code {
+            "NoSuchKey" => S3ErrorKind::NoSuchKey,
+            "NoSuchBucket" => S3ErrorKind::NoSuchBucket,
+            "AccessDenied" => S3ErrorKind::AccessDenied,
+            "BucketAlreadyExists" => S3ErrorKind::BucketAlreadyExists,
+            "BucketAlreadyOwnedByYou" => S3ErrorKind::BucketAlreadyOwnedByYou,
+            "InvalidRange" => S3ErrorKind::InvalidRange,
+            "NotModified" => S3ErrorKind::NotModified,
+            "PreconditionFailed" => S3ErrorKind::PreconditionFailed,
+            "SlowDown" => S3ErrorKind::SlowDown,
+            "RequestTimeTooSkewed" => S3ErrorKind::RequestTimeTooSkewed,
+            "SignatureDoesNotMatch" => S3ErrorKind::SignatureDoesNotMatch,
+            _ => S3ErrorKind::Unknown(code.to_string()),
+        }
+    }
+
+    /// Maps an HTTP status to a kind. Only used when the response has no error code.
+    pub fn from_status(status: u16) -> S3ErrorKind {
+        match status {
+            304 => S3ErrorKind::NotModified,
+            403 => S3ErrorKind::AccessDenied,
+            412 => S3ErrorKind::PreconditionFailed,
+            416 => S3ErrorKind::InvalidRange,
+            503 => S3ErrorKind::SlowDown,
+            _ => S3ErrorKind::Unknown(String::new()),
+        }
+    }
+}
+
 impl S3Error {
     /// implements the basic S3Error without a valid `AWSError` (default).
     pub fn new<S>(message: S) -> S3Error where S: Into<String> {
-        S3Error { message: message.into(), aws: AWSError::default() }
+        S3Error::with_kind(S3ErrorKind::default(), message)
+    }
+
+    /// implements the S3Error of a given kind without a valid `AWSError` (default).
+    pub fn with_kind<S>(kind: S3ErrorKind, message: S) -> S3Error where S: Into<String> {
+        S3Error { kind: kind, status: 0, message: message.into(), aws: AWSError::default() }
     }
 
     /// implements the S3Error with a specific `AWSError` with details about the AWS error.
     pub fn with_aws<S>(message: S, aws: AWSError) -> S3Error where S: Into<String> {
-        S3Error { message: message.into(), aws: aws }
+        S3Error::with_status(message, 0, aws)
+    }
+
+    /// implements the S3Error for a failed response. The kind comes from the AWS error code or
+    /// from the HTTP status if there is no code.
+    pub fn with_status<S>(message: S, status: u16, aws: AWSError) -> S3Error where S: Into<String> {
+        let kind = if aws.code.is_empty() {
+            S3ErrorKind::from_status(status)
+        } else {
+            S3ErrorKind::from_code(&aws.code)
+        };
+        S3Error { kind: kind, status: status, message: message.into(), aws: aws }
     }
 }
 
@@ -84,26 +177,33 @@ impl Error for S3Error {
 
 impl From<CredentialsError> for S3Error {
     fn from(err: CredentialsError) -> S3Error {
-        S3Error { message: err.description().to_owned(), aws: AWSError::default() }
+        S3Error::with_kind(S3ErrorKind::Credentials, err.description())
     }
 }
 
 impl From<ParseIntError> for S3Error {
     fn from(err: ParseIntError) -> S3Error {
-        S3Error { message: err.description().to_owned(), aws: AWSError::default() }
+        S3Error::with_kind(S3ErrorKind::HeaderParse, err.description())
     }
 }
 
 impl From<ParseBoolError> for S3Error {
     fn from(err: ParseBoolError) -> S3Error {
-        S3Error { message: err.description().to_owned(), aws: AWSError::default() }
+        S3Error::with_kind(S3ErrorKind::HeaderParse, err.description())
     }
 }
 
 impl From<XmlParseError> for S3Error {
     fn from(err: XmlParseError) -> S3Error {
         let XmlParseError(message) = err;
-        S3Error { message: message.to_owned(), aws: AWSError::default() }
+        S3Error::with_kind(S3ErrorKind::XmlParse, message)
+    }
+}
+
+impl From<HttpDispatchError> for S3Error {
+    fn from(err: HttpDispatchError) -> S3Error {
+        let kind = if err.is_timeout() { S3ErrorKind::Timeout } else { S3ErrorKind::Transport };
+        S3Error::with_kind(kind, err.description())
     }
 }
 
@@ -146,3 +246,37 @@ impl S3ClientErrorWriter {
         ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io;
+
+    use aws::common::request::HttpDispatchError;
+    use aws::errors::aws::AWSError;
+
+    #[test]
+    fn error_kind_from_code_and_status() {
+        let mut aws = AWSError::default();
+        aws.code = "NoSuchKey".to_string();
+        let err = S3Error::with_status("Error getting object", 404, aws);
+        assert_eq!(err.kind, S3ErrorKind::NoSuchKey);
+        assert_eq!(err.status, 404);
+
+        let err = S3Error::with_status("Error getting object head", 412, AWSError::default());
+        assert_eq!(err.kind, S3ErrorKind::PreconditionFailed);
+
+        assert_eq!(S3ErrorKind::from_code("InvalidBucketName"), S3ErrorKind::Unknown("InvalidBucketName".to_string()));
+        assert_eq!(S3Error::new("bad input").kind, S3ErrorKind::Unknown(String::new()));
+    }
+
+    #[test]
+    fn error_kind_from_dispatch_error() {
+        let err = S3Error::from(HttpDispatchError::from(io::Error::new(io::ErrorKind::TimedOut, "timed out")));
+        assert_eq!(err.kind, S3ErrorKind::Timeout);
+
+        let err = S3Error::from(HttpDispatchError::from(io::Error::new(io::ErrorKind::ConnectionReset, "reset")));
+        assert_eq!(err.kind, S3ErrorKind::Transport);
+    }
+}
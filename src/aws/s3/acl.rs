@@ -14,6 +14,8 @@
 //
 
 #![allow(unused_variables)]
+use xml::escape::escape_str_pcdata;
+
 use aws::common::params::{Params, ServiceParams};
 use aws::common::xmlutil::*;
 use aws::common::common::*;
@@ -113,23 +115,21 @@ pub struct AccessControlList {
     pub grants: Grants,
 }
 
-// NOTE: May remove this one...
-//
-// #[derive(Debug, Default)]
-// pub struct GetBucketAclOutput {
-// pub owner: Owner,
-// pub acl: AccessControlList,
-// pub grants: Grants,
-// }
-//
-
-
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct GetBucketAclRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetBucketAclOutput {
+    pub owner: Owner,
+    /// A list of grants.
+    pub grants: Grants,
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct PutBucketAclRequest {
@@ -151,6 +151,12 @@ pub struct PutBucketAclRequest {
     pub grant_read_acp: Option<GrantReadACP>,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketAclOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct GetObjectAclRequest {
@@ -191,12 +197,14 @@ pub struct GetObjectAclOutput {
     /// A list of grants.
     pub grants: Grants,
     pub request_charged: RequestCharged,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct PutObjectAclOutput {
     pub request_charged: RequestCharged,
+    pub response_metadata: ResponseMetadata,
 }
 
 // Impls below...
@@ -441,6 +449,24 @@ impl PermissionWriter {
     }
 }
 
+/// Writes out the `AccessControlPolicy` body used by put bucket and object acl requests.
+pub fn access_control_policy_xml(policy: &AccessControlPolicy) -> Vec<u8> {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    xml.push_str("<AccessControlPolicy xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">");
+    xml.push_str(&format!("<Owner><ID>{}</ID>", escape_str_pcdata(&policy.owner.id)));
+    if !policy.owner.display_name.is_empty() {
+        xml.push_str(&format!("<DisplayName>{}</DisplayName>", escape_str_pcdata(&policy.owner.display_name)));
+    }
+    xml.push_str("</Owner><AccessControlList>");
+    for grant in &policy.acl.grants {
+        xml.push_str(&format!("<Grant>{}<Permission>{}</Permission></Grant>",
+                              grantee_xml(&grant.grantee),
+                              escape_str_pcdata(&grant.permission)));
+    }
+    xml.push_str("</AccessControlList></AccessControlPolicy>");
+    xml.into_bytes()
+}
+
 /// Maps canned acl to AWS format.
 pub fn canned_acl_in_aws_format(canned_acl: &CannedAcl) -> String {
     match *canned_acl {
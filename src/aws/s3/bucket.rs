@@ -19,10 +19,12 @@
 #![allow(unused_variables)]
 #![allow(unused_mut)]
 
+use xml::escape::escape_str_pcdata;
+
 use aws::common::region::Region;
 use aws::common::params::{Params, ServiceParams};
 use aws::common::xmlutil::*;
-// use aws::common::common::*;
+use aws::common::common::ID;
 use aws::errors::http::*;
 use aws::s3::writeparse::*;
 use aws::s3::object::*;
@@ -39,6 +41,13 @@ pub type CloudFunctionConfigurationList = Vec<CloudFunctionConfiguration>;
 
 pub type TopicConfigurationList = Vec<TopicConfiguration>;
 
+/// Object key name filter rule name. Either prefix or suffix.
+pub type FilterRuleName = String;
+
+pub type FilterRuleValue = String;
+
+pub type FilterRuleList = Vec<FilterRule>;
+
 pub type BucketLocationConstraint = String;
 
 pub type TargetBucket = String;
@@ -87,6 +96,18 @@ pub struct NotificationConfigurationParser;
 /// Write `NotificationConfiguration` contents to a `SignedRequest`
 pub struct NotificationConfigurationWriter;
 
+/// Parse `NotificationConfigurationFilter` from XML
+pub struct NotificationConfigurationFilterParser;
+
+/// Parse `S3KeyFilter` from XML
+pub struct S3KeyFilterParser;
+
+/// Parse `FilterRule` from XML
+pub struct FilterRuleParser;
+
+/// Parse `FilterRuleList` from XML
+pub struct FilterRuleListParser;
+
 /// The requested bucket name is not available. The bucket namespace is shared by
 /// all users of the system. Please select a different name and try again.
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
@@ -174,6 +195,13 @@ pub struct PutBucketReplicationRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketReplicationOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
+/// Uses the deprecated notification configuration. See `PutBucketNotificationConfigurationRequest`.
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct PutBucketNotificationRequest {
@@ -196,6 +224,45 @@ pub struct PutBucketLifecycleRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketLifecycleOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetBucketLifecycleRequest {
+    pub bucket: BucketName,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetBucketLifecycleOutput {
+    pub lifecycle_configuration: LifecycleConfiguration,
+    pub response_metadata: ResponseMetadata,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetBucketPolicyRequest {
+    pub bucket: BucketName,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetBucketPolicyOutput {
+    /// The bucket policy as a JSON document.
+    pub policy: Policy,
+    pub response_metadata: ResponseMetadata,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetBucketReplicationRequest {
+    pub bucket: BucketName,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct GetBucketCorsRequest {
@@ -206,6 +273,7 @@ pub struct GetBucketCorsRequest {
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct GetBucketCorsOutput {
     pub cors_rules: CORSRules,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
@@ -224,6 +292,7 @@ pub struct GetBucketVersioningOutput {
     /// with MFA delete. If the bucket has never been so configured, this element is
     /// not returned.
     pub mfa_delete: MFADeleteStatus,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
@@ -237,6 +306,12 @@ pub struct PutBucketVersioningRequest {
     pub versioning_configuration: VersioningConfiguration,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketVersioningOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct CORSRule {
@@ -254,6 +329,15 @@ pub struct CORSRule {
     pub max_age_seconds: MaxAgeSeconds,
     /// One or more origins you want customers to be able to access the bucket from.
     pub allowed_origins: AllowedOrigins,
+    /// Unique identifier for the rule. The value cannot be longer than 255 characters.
+    pub id: Option<ID>,
+}
+
+/// Container for up to 100 CORS rules.
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct CORSConfiguration {
+    pub cors_rules: CORSRules,
 }
 
 //#[derive(Debug, Default)]
@@ -274,6 +358,26 @@ pub struct DeleteBucketCorsRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct DeleteBucketCorsOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketCorsRequest {
+    pub cors_configuration: CORSConfiguration,
+    pub content_md5: Option<ContentMD5>,
+    pub bucket: BucketName,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketCorsOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 /// Container for specifying the notification configuration of the bucket. If this
 /// element is empty, notifications are turned off on the bucket.
 //#[derive(Debug, Default)]
@@ -285,6 +389,44 @@ pub struct NotificationConfiguration {
     pub cloud_function_configurations: CloudFunctionConfigurationList,
 }
 
+/// Container for the object key name filter of a notification configuration.
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct NotificationConfigurationFilter {
+    pub key: S3KeyFilter,
+}
+
+/// Container for the prefix and suffix rules an object key name must match.
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct S3KeyFilter {
+    pub filter_rules: FilterRuleList,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct FilterRule {
+    /// Object key name prefix or suffix.
+    pub name: FilterRuleName,
+    pub value: FilterRuleValue,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketNotificationConfigurationRequest {
+    /// Replaces the notification configuration of the bucket. An empty configuration turns
+    /// off notifications.
+    pub notification_configuration: NotificationConfiguration,
+    pub content_md5: Option<ContentMD5>,
+    pub bucket: BucketName,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketNotificationConfigurationOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct GetBucketNotificationConfigurationRequest {
@@ -292,18 +434,65 @@ pub struct GetBucketNotificationConfigurationRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetBucketNotificationConfigurationOutput {
+    pub notification_configuration: NotificationConfiguration,
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct DeleteBucketWebsiteRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct DeleteBucketWebsiteOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct DeleteBucketTaggingRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct DeleteBucketTaggingOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketTaggingRequest {
+    /// Replaces any tags already on the bucket.
+    pub tagging: Tagging,
+    pub content_md5: Option<ContentMD5>,
+    pub bucket: BucketName,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketTaggingOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetBucketTaggingRequest {
+    pub bucket: BucketName,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetBucketTaggingOutput {
+    pub tagging: Tagging,
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct LifecycleConfiguration {
@@ -320,6 +509,12 @@ pub struct PutBucketPolicyRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketPolicyOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct RoutingRule {
@@ -362,6 +557,12 @@ pub struct PutBucketLoggingRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketLoggingOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 /// Container for replication rules. You can add as many as 1,000 rules. Total
 /// replication configuration size can be up to 2 MB.
 //#[derive(Debug, Default)]
@@ -412,6 +613,8 @@ pub struct Destination {
     /// Amazon resource name (ARN) of the bucket where you want Amazon S3 to store
     /// replicas of the object identified by the rule.
     pub bucket: BucketName,
+    /// Storage class of the replicas. Defaults to the storage class of the source object.
+    pub storage_class: Option<StorageClass>,
 }
 
 //#[derive(Debug, Default)]
@@ -443,28 +646,48 @@ pub struct DeleteBucketRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct DeleteBucketOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct DeleteBucketPolicyRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct DeleteBucketPolicyOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct DeleteBucketReplicationRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct DeleteBucketReplicationOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct GetBucketLoggingOutput {
     pub logging_enabled: LoggingEnabled,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct GetBucketReplicationOutput {
     pub replication_configuration: ReplicationConfiguration,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
@@ -475,11 +698,17 @@ pub struct PutBucketWebsiteRequest {
     pub website_configuration: WebsiteConfiguration,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutBucketWebsiteOutput {
+    pub response_metadata: ResponseMetadata,
+}
+
 // Impls and functions below...
 
 /// Helper function to determine if a create config is needed.
-pub fn needs_create_bucket_config(region: Region) -> bool {
-    match region {
+pub fn needs_create_bucket_config(region: &Region) -> bool {
+    match *region {
         Region::UsEast1 => false,
         _ => true,
     }
@@ -487,8 +716,8 @@ pub fn needs_create_bucket_config(region: Region) -> bool {
 
 // This is a bit hacky to get functionality until we figure out an XML writing util.
 /// Manually writes out bucket configuration (location constraint) in XML.
-pub fn create_bucket_config_xml(region: Region) -> Vec<u8> {
-    match region {
+pub fn create_bucket_config_xml(region: &Region) -> Vec<u8> {
+    match *region {
         Region::UsEast1 => {
             Vec::new() // shouldn't actually execute this: panic! or unreachable! this?
         },
@@ -502,6 +731,274 @@ pub fn create_bucket_config_xml(region: Region) -> Vec<u8> {
     }
 }
 
+/// Writes out the `LifecycleConfiguration` body of a put bucket lifecycle request.
+pub fn lifecycle_configuration_xml(config: &LifecycleConfiguration) -> Vec<u8> {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?><LifecycleConfiguration>");
+
+    for rule in &config.rules {
+        xml.push_str("<Rule>");
+        if let Some(ref id) = rule.id {
+            xml_element(&mut xml, "ID", id);
+        }
+        match rule.filter {
+            Some(ref filter) => lifecycle_filter_xml(&mut xml, filter),
+            // Prefix is required even when it's empty (all objects)
+            None => xml.push_str(&format!("<Prefix>{}</Prefix>", escape_str_pcdata(&rule.prefix))),
+        }
+        xml_element(&mut xml, "Status", &rule.status);
+        if let Some(ref transition) = rule.transition {
+            xml.push_str("<Transition>");
+            xml_element(&mut xml, "Date", &transition.date);
+            xml_number(&mut xml, "Days", transition.days);
+            xml_element(&mut xml, "StorageClass", &transition.storage_class);
+            xml.push_str("</Transition>");
+        }
+        if let Some(ref expiration) = rule.expiration {
+            xml.push_str("<Expiration>");
+            xml_element(&mut xml, "Date", &expiration.date);
+            xml_number(&mut xml, "Days", expiration.days);
+            xml.push_str("</Expiration>");
+        }
+        if let Some(ref transition) = rule.noncurrent_version_transition {
+            xml.push_str("<NoncurrentVersionTransition>");
+            xml_number(&mut xml, "NoncurrentDays", transition.noncurrent_days);
+            xml_element(&mut xml, "StorageClass", &transition.storage_class);
+            xml.push_str("</NoncurrentVersionTransition>");
+        }
+        if let Some(ref expiration) = rule.noncurrent_version_expiration {
+            xml.push_str("<NoncurrentVersionExpiration>");
+            xml_number(&mut xml, "NoncurrentDays", expiration.noncurrent_days);
+            xml.push_str("</NoncurrentVersionExpiration>");
+        }
+        xml.push_str("</Rule>");
+    }
+
+    xml.push_str("</LifecycleConfiguration>");
+    xml.into_bytes()
+}
+
+// A filter with a prefix and tags (or more than one tag) needs the And element. A prefix only
+// filter always writes the Prefix since an empty one means all objects.
+fn lifecycle_filter_xml(xml: &mut String, filter: &LifecycleRuleFilter) {
+    let and = filter.tags.len() > 1 || (!filter.tags.is_empty() && !filter.prefix.is_empty());
+
+    xml.push_str("<Filter>");
+    if and {
+        xml.push_str("<And>");
+    }
+    if filter.tags.is_empty() {
+        xml.push_str(&format!("<Prefix>{}</Prefix>", escape_str_pcdata(&filter.prefix)));
+    } else {
+        xml_element(xml, "Prefix", &filter.prefix);
+    }
+    for tag in &filter.tags {
+        xml.push_str(&format!("<Tag><Key>{}</Key><Value>{}</Value></Tag>",
+                              escape_str_pcdata(&tag.key),
+                              escape_str_pcdata(&tag.value)));
+    }
+    if and {
+        xml.push_str("</And>");
+    }
+    xml.push_str("</Filter>");
+}
+
+/// Writes out the `CORSConfiguration` body of a put bucket cors request.
+pub fn cors_configuration_xml(config: &CORSConfiguration) -> Vec<u8> {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?><CORSConfiguration>");
+
+    for rule in &config.cors_rules {
+        xml.push_str("<CORSRule>");
+        if let Some(ref id) = rule.id {
+            xml_element(&mut xml, "ID", id);
+        }
+        for origin in &rule.allowed_origins {
+            xml_element(&mut xml, "AllowedOrigin", origin);
+        }
+        for method in &rule.allowed_methods {
+            xml_element(&mut xml, "AllowedMethod", method);
+        }
+        for header in &rule.allowed_headers {
+            xml_element(&mut xml, "AllowedHeader", header);
+        }
+        for header in &rule.expose_headers {
+            xml_element(&mut xml, "ExposeHeader", header);
+        }
+        xml_number(&mut xml, "MaxAgeSeconds", rule.max_age_seconds);
+        xml.push_str("</CORSRule>");
+    }
+
+    xml.push_str("</CORSConfiguration>");
+    xml.into_bytes()
+}
+
+/// Writes out the `WebsiteConfiguration` body of a put bucket website request. If
+/// `redirect_all_requests_to` has a host name then the other settings are not written since S3
+/// does not allow them together.
+pub fn website_configuration_xml(config: &WebsiteConfiguration) -> Vec<u8> {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+                                <WebsiteConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">");
+
+    if !config.redirect_all_requests_to.host_name.is_empty() {
+        xml.push_str("<RedirectAllRequestsTo>");
+        xml_element(&mut xml, "HostName", &config.redirect_all_requests_to.host_name);
+        if let Some(ref protocol) = config.redirect_all_requests_to.protocol {
+            xml_element(&mut xml, "Protocol", protocol);
+        }
+        xml.push_str("</RedirectAllRequestsTo>");
+    } else {
+        if !config.index_document.suffix.is_empty() {
+            xml.push_str("<IndexDocument>");
+            xml_element(&mut xml, "Suffix", &config.index_document.suffix);
+            xml.push_str("</IndexDocument>");
+        }
+        if !config.error_document.key.is_empty() {
+            xml.push_str("<ErrorDocument>");
+            xml_element(&mut xml, "Key", &config.error_document.key);
+            xml.push_str("</ErrorDocument>");
+        }
+        if !config.routing_rules.is_empty() {
+            xml.push_str("<RoutingRules>");
+            for rule in &config.routing_rules {
+                xml.push_str("<RoutingRule>");
+                if let Some(ref condition) = rule.condition {
+                    xml.push_str("<Condition>");
+                    xml_element(&mut xml, "HttpErrorCodeReturnedEquals", &condition.http_error_code_returned_equals);
+                    xml_element(&mut xml, "KeyPrefixEquals", &condition.key_prefix_equals);
+                    xml.push_str("</Condition>");
+                }
+                xml.push_str("<Redirect>");
+                xml_element(&mut xml, "Protocol", &rule.redirect.protocol);
+                xml_element(&mut xml, "HostName", &rule.redirect.host_name);
+                xml_element(&mut xml, "ReplaceKeyPrefixWith", &rule.redirect.replace_key_prefix_with);
+                xml_element(&mut xml, "ReplaceKeyWith", &rule.redirect.replace_key_with);
+                xml_element(&mut xml, "HttpRedirectCode", &rule.redirect.http_redirect_code);
+                xml.push_str("</Redirect></RoutingRule>");
+            }
+            xml.push_str("</RoutingRules>");
+        }
+    }
+
+    xml.push_str("</WebsiteConfiguration>");
+    xml.into_bytes()
+}
+
+/// Writes out the `BucketLoggingStatus` body of a put bucket logging request. An empty
+/// `target_bucket` writes an empty status which disables logging.
+pub fn bucket_logging_status_xml(status: &BucketLoggingStatus) -> Vec<u8> {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+                                <BucketLoggingStatus xmlns=\"http://doc.s3.amazonaws.com/2006-03-01\">");
+
+    let logging = &status.logging_enabled;
+    if !logging.target_bucket.is_empty() {
+        xml.push_str("<LoggingEnabled>");
+        xml_element(&mut xml, "TargetBucket", &logging.target_bucket);
+        xml.push_str(&format!("<TargetPrefix>{}</TargetPrefix>", escape_str_pcdata(&logging.target_prefix)));
+        if !logging.target_grants.is_empty() {
+            xml.push_str("<TargetGrants>");
+            for grant in &logging.target_grants {
+                xml.push_str(&format!("<Grant>{}<Permission>{}</Permission></Grant>",
+                                      grantee_xml(&grant.grantee),
+                                      escape_str_pcdata(&grant.permission)));
+            }
+            xml.push_str("</TargetGrants>");
+        }
+        xml.push_str("</LoggingEnabled>");
+    }
+
+    xml.push_str("</BucketLoggingStatus>");
+    xml.into_bytes()
+}
+
+/// Writes out the `ReplicationConfiguration` body of a put bucket replication request.
+pub fn replication_configuration_xml(config: &ReplicationConfiguration) -> Vec<u8> {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?><ReplicationConfiguration>");
+    xml_element(&mut xml, "Role", &config.role);
+
+    for rule in &config.rules {
+        xml.push_str("<Rule>");
+        if let Some(ref id) = rule.id {
+            xml_element(&mut xml, "ID", id);
+        }
+        xml.push_str(&format!("<Prefix>{}</Prefix>", escape_str_pcdata(&rule.prefix)));
+        xml_element(&mut xml, "Status", &rule.status);
+        xml.push_str("<Destination>");
+        xml_element(&mut xml, "Bucket", &rule.destination.bucket);
+        if let Some(ref storage_class) = rule.destination.storage_class {
+            xml_element(&mut xml, "StorageClass", storage_class);
+        }
+        xml.push_str("</Destination></Rule>");
+    }
+
+    xml.push_str("</ReplicationConfiguration>");
+    xml.into_bytes()
+}
+
+/// Writes out the `NotificationConfiguration` body of a put bucket notification
+/// configuration request. The deprecated `CloudFunctionConfiguration` entries are not
+/// accepted by the API so they are not written.
+pub fn notification_configuration_xml(config: &NotificationConfiguration) -> Vec<u8> {
+    let mut xml = String::from("<NotificationConfiguration>");
+
+    for topic in &config.topic_configurations {
+        notification_xml(&mut xml, "TopicConfiguration", "Topic", &topic.topic_arn,
+                         &topic.id, &topic.events, &topic.filter);
+    }
+    for queue in &config.queue_configurations {
+        notification_xml(&mut xml, "QueueConfiguration", "Queue", &queue.queue_arn,
+                         &queue.id, &queue.events, &queue.filter);
+    }
+    for lambda in &config.lambda_function_configurations {
+        notification_xml(&mut xml, "LambdaFunctionConfiguration", "CloudFunction", &lambda.lambda_function_arn,
+                         &lambda.id, &lambda.events, &lambda.filter);
+    }
+
+    xml.push_str("</NotificationConfiguration>");
+    xml.into_bytes()
+}
+
+// Topic, queue and lambda configurations only differ by their element and ARN names.
+fn notification_xml(xml: &mut String,
+                    name: &str,
+                    arn_name: &str,
+                    arn: &str,
+                    id: &Option<NotificationId>,
+                    events: &EventList,
+                    filter: &Option<NotificationConfigurationFilter>) {
+    xml.push_str(&format!("<{}>", name));
+    if let Some(ref id) = *id {
+        xml_element(xml, "Id", id);
+    }
+    xml_element(xml, arn_name, arn);
+    for event in events {
+        xml_element(xml, "Event", event);
+    }
+    if let Some(ref filter) = *filter {
+        xml.push_str("<Filter><S3Key>");
+        for rule in &filter.key.filter_rules {
+            xml.push_str("<FilterRule>");
+            xml_element(xml, "Name", &rule.name);
+            xml_element(xml, "Value", &rule.value);
+            xml.push_str("</FilterRule>");
+        }
+        xml.push_str("</S3Key></Filter>");
+    }
+    xml.push_str(&format!("</{}>", name));
+}
+
+// Appends `<name>value</name>` with the value escaped. Empty values are skipped.
+fn xml_element(xml: &mut String, name: &str, value: &str) {
+    if !value.is_empty() {
+        xml.push_str(&format!("<{}>{}</{}>", name, escape_str_pcdata(value), name));
+    }
+}
+
+// Appends a numeric element such as a day count. Zero means not set and is skipped.
+fn xml_number(xml: &mut String, name: &str, value: i32) {
+    if value > 0 {
+        xml.push_str(&format!("<{}>{}</{}>", name, value, name));
+    }
+}
+
 impl BucketNameParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<BucketName, XmlParseError> {
         try!(start_element(tag_name, stack));
@@ -626,7 +1123,11 @@ impl TopicConfigurationParser {
                 continue;
             }
             if current_name == "Event" {
-                obj.events = try!(EventListParser::parse_xml("Event", stack));
+                obj.events.extend(try!(EventListParser::parse_xml("Event", stack)));
+                continue;
+            }
+            if current_name == "Filter" {
+                obj.filter = Some(try!(NotificationConfigurationFilterParser::parse_xml("Filter", stack)));
                 continue;
             }
             break;
@@ -688,6 +1189,76 @@ impl NotificationConfigurationWriter {
     }
 }
 
+impl NotificationConfigurationFilterParser {
+    pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<NotificationConfigurationFilter, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = NotificationConfigurationFilter::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "S3Key" {
+                obj.key = try!(S3KeyFilterParser::parse_xml("S3Key", stack));
+                continue;
+            }
+            // NOTE: Ceph RGW adds S3Metadata and S3Tags filters which are skipped.
+            if !current_name.is_empty() {
+                skip_tree(stack);
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+
+impl S3KeyFilterParser {
+    pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<S3KeyFilter, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = S3KeyFilter::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "FilterRule" {
+                obj.filter_rules.extend(try!(FilterRuleListParser::parse_xml("FilterRule", stack)));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+
+impl FilterRuleListParser {
+    pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<FilterRuleList, XmlParseError> {
+        let mut obj = Vec::new();
+        while try!(peek_at_name(stack)) == tag_name {
+            obj.push(try!(FilterRuleParser::parse_xml(tag_name, stack)));
+        }
+        Ok(obj)
+    }
+}
+
+impl FilterRuleParser {
+    pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<FilterRule, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = FilterRule::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Name" {
+                obj.name = try!(string_field("Name", stack));
+                continue;
+            }
+            if current_name == "Value" {
+                obj.value = try!(string_field("Value", stack));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+
 impl BucketAlreadyExistsParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<BucketAlreadyExists, XmlParseError> {
         try!(start_element(tag_name, stack));
@@ -810,8 +1381,10 @@ impl LoggingEnabledParser {
                 obj.target_bucket = try!(TargetBucketParser::parse_xml("TargetBucket", stack));
                 continue;
             }
-            if current_name == "Grant" {
+            if current_name == "TargetGrants" {
+                try!(start_element("TargetGrants", stack));
                 obj.target_grants = try!(TargetGrantsParser::parse_xml("Grant", stack));
+                try!(end_element("TargetGrants", stack));
                 continue;
             }
             break;
@@ -830,3 +1403,202 @@ impl LoggingEnabledWriter {
         TargetGrantsWriter::write_params(params, &(prefix.to_string() + "Grant"), &obj.target_grants);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str;
+    use xml::EventReader;
+    use aws::common::xmlutil::XmlResponse;
+
+    #[test]
+    fn website_configuration_round_trip() {
+        let mut config = WebsiteConfiguration::default();
+        config.index_document.suffix = "index.html".to_string();
+        config.error_document.key = "error.html".to_string();
+        let mut rule = RoutingRule::default();
+        rule.condition = Some(Condition { http_error_code_returned_equals: String::new(),
+                                          key_prefix_equals: "docs/".to_string() });
+        rule.redirect.replace_key_prefix_with = "documents/".to_string();
+        config.routing_rules.push(rule);
+
+        let xml = website_configuration_xml(&config);
+        let body = str::from_utf8(&xml).unwrap();
+        let mut reader = EventReader::from_str(body);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next();
+
+        let parsed = WebsiteConfigurationParser::parse_xml("WebsiteConfiguration", &mut stack).unwrap();
+        assert_eq!(parsed.index_document.suffix, "index.html");
+        assert_eq!(parsed.error_document.key, "error.html");
+        assert_eq!(parsed.routing_rules.len(), 1);
+        assert_eq!(parsed.routing_rules[0].condition.as_ref().unwrap().key_prefix_equals, "docs/");
+        assert_eq!(parsed.routing_rules[0].redirect.replace_key_prefix_with, "documents/");
+    }
+
+    #[test]
+    fn lifecycle_configuration_round_trip() {
+        let mut config = LifecycleConfiguration::default();
+        let mut rule = Rule::default();
+        rule.id = Some("archive".to_string());
+        rule.status = "Enabled".to_string();
+        rule.filter = Some(LifecycleRuleFilter {
+            prefix: "logs/".to_string(),
+            tags: vec![Tag { key: "class".to_string(), value: "cold".to_string() }],
+        });
+        rule.transition = Some(Transition { date: String::new(), days: 30, storage_class: "GLACIER".to_string() });
+        rule.expiration = Some(LifecycleExpiration { date: String::new(), days: 365 });
+        config.rules.push(rule);
+        let mut rule = Rule::default();
+        rule.status = "Disabled".to_string();
+        rule.prefix = "tmp/".to_string();
+        rule.noncurrent_version_expiration = Some(NoncurrentVersionExpiration { noncurrent_days: 7 });
+        config.rules.push(rule);
+
+        let xml = lifecycle_configuration_xml(&config);
+        let body = str::from_utf8(&xml).unwrap();
+        assert!(body.contains("<ID>archive</ID><Filter><And><Prefix>logs/</Prefix><Tag>"));
+        let mut reader = EventReader::from_str(body);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next();
+
+        let parsed = LifecycleConfigurationParser::parse_xml("LifecycleConfiguration", &mut stack).unwrap();
+        assert_eq!(parsed.rules.len(), 2);
+        let rule = &parsed.rules[0];
+        assert_eq!(rule.id, Some("archive".to_string()));
+        assert_eq!(rule.status, "Enabled");
+        let filter = rule.filter.as_ref().unwrap();
+        assert_eq!(filter.prefix, "logs/");
+        assert_eq!(filter.tags, vec![Tag { key: "class".to_string(), value: "cold".to_string() }]);
+        assert_eq!(rule.transition.as_ref().unwrap().days, 30);
+        assert_eq!(rule.transition.as_ref().unwrap().storage_class, "GLACIER");
+        assert_eq!(rule.expiration.as_ref().unwrap().days, 365);
+        let rule = &parsed.rules[1];
+        assert!(rule.filter.is_none());
+        assert_eq!(rule.prefix, "tmp/");
+        assert_eq!(rule.noncurrent_version_expiration.as_ref().unwrap().noncurrent_days, 7);
+    }
+
+    #[test]
+    fn bucket_logging_status_round_trip() {
+        let mut status = BucketLoggingStatus::default();
+        status.logging_enabled.target_bucket = "logs".to_string();
+        status.logging_enabled.target_prefix = "access/".to_string();
+        let mut grant = TargetGrant::default();
+        grant.grantee.uri = Some("http://acs.amazonaws.com/groups/global/AllUsers".to_string());
+        grant.permission = "READ".to_string();
+        status.logging_enabled.target_grants.push(grant);
+
+        let xml = bucket_logging_status_xml(&status);
+        let body = str::from_utf8(&xml).unwrap();
+        let mut reader = EventReader::from_str(body);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next();
+
+        let parsed = BucketLoggingStatusParser::parse_xml("BucketLoggingStatus", &mut stack).unwrap();
+        let logging = &parsed.logging_enabled;
+        assert_eq!(logging.target_bucket, "logs");
+        assert_eq!(logging.target_prefix, "access/");
+        assert_eq!(logging.target_grants.len(), 1);
+        assert_eq!(logging.target_grants[0].permission, "READ");
+        assert_eq!(logging.target_grants[0].grantee.uri, Some("http://acs.amazonaws.com/groups/global/AllUsers".to_string()));
+    }
+
+    #[test]
+    fn replication_configuration_round_trip() {
+        let mut config = ReplicationConfiguration::default();
+        config.role = "arn:aws:iam::123456789012:role/replication".to_string();
+        let mut rule = ReplicationRule::default();
+        rule.id = Some("backup".to_string());
+        rule.status = "Enabled".to_string();
+        rule.prefix = "data/".to_string();
+        rule.destination.bucket = "arn:aws:s3:::backup".to_string();
+        rule.destination.storage_class = Some("STANDARD_IA".to_string());
+        config.rules.push(rule);
+
+        let xml = replication_configuration_xml(&config);
+        let body = str::from_utf8(&xml).unwrap();
+        let mut reader = EventReader::from_str(body);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next();
+
+        let parsed = ReplicationConfigurationParser::parse_xml("ReplicationConfiguration", &mut stack).unwrap();
+        assert_eq!(parsed.role, "arn:aws:iam::123456789012:role/replication");
+        assert_eq!(parsed.rules.len(), 1);
+        let rule = &parsed.rules[0];
+        assert_eq!(rule.id, Some("backup".to_string()));
+        assert_eq!(rule.status, "Enabled");
+        assert_eq!(rule.prefix, "data/");
+        assert_eq!(rule.destination.bucket, "arn:aws:s3:::backup");
+        assert_eq!(rule.destination.storage_class, Some("STANDARD_IA".to_string()));
+    }
+
+    #[test]
+    fn notification_configuration_round_trip() {
+        let mut config = NotificationConfiguration::default();
+        let mut queue = QueueConfiguration::default();
+        queue.id = Some("uploads".to_string());
+        queue.queue_arn = "arn:aws:sqs:us-east-1:123456789012:uploads".to_string();
+        queue.events = vec!["s3:ObjectCreated:Put".to_string(), "s3:ObjectCreated:Post".to_string()];
+        let mut filter = NotificationConfigurationFilter::default();
+        filter.key.filter_rules.push(FilterRule { name: "prefix".to_string(), value: "images/".to_string() });
+        filter.key.filter_rules.push(FilterRule { name: "suffix".to_string(), value: ".jpg".to_string() });
+        queue.filter = Some(filter);
+        config.queue_configurations.push(queue);
+        let mut topic = TopicConfiguration::default();
+        topic.topic_arn = "arn:aws:sns:us-east-1:123456789012:deletes".to_string();
+        topic.events = vec!["s3:ObjectRemoved:*".to_string()];
+        config.topic_configurations.push(topic);
+
+        let xml = notification_configuration_xml(&config);
+        let body = str::from_utf8(&xml).unwrap();
+        let mut reader = EventReader::from_str(body);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next();
+
+        let parsed = NotificationConfigurationParser::parse_xml("NotificationConfiguration", &mut stack).unwrap();
+        assert_eq!(parsed.topic_configurations.len(), 1);
+        assert!(parsed.topic_configurations[0].filter.is_none());
+        assert_eq!(parsed.queue_configurations.len(), 1);
+        assert_eq!(parsed.queue_configurations[0].events.len(), 2);
+        let rules = &parsed.queue_configurations[0].filter.as_ref().unwrap().key.filter_rules;
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[1].name, "suffix");
+        assert_eq!(rules[1].value, ".jpg");
+    }
+
+    #[test]
+    fn cors_configuration_round_trip() {
+        let mut config = CORSConfiguration::default();
+        let mut rule = CORSRule::default();
+        rule.id = Some("web".to_string());
+        rule.allowed_origins = vec!["https://example.com".to_string(), "https://www.example.com".to_string()];
+        rule.allowed_methods = vec!["GET".to_string(), "PUT".to_string()];
+        rule.allowed_headers = vec!["*".to_string()];
+        rule.expose_headers = vec!["ETag".to_string()];
+        rule.max_age_seconds = 3000;
+        config.cors_rules.push(rule);
+        let mut rule = CORSRule::default();
+        rule.allowed_origins = vec!["*".to_string()];
+        rule.allowed_methods = vec!["GET".to_string()];
+        config.cors_rules.push(rule);
+
+        let xml = cors_configuration_xml(&config);
+        let body = str::from_utf8(&xml).unwrap();
+        let mut reader = EventReader::from_str(body);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next();
+
+        let parsed = GetBucketCorsOutputParser::parse_xml("CORSConfiguration", &mut stack).unwrap();
+        assert_eq!(parsed.cors_rules.len(), 2);
+        let rule = &parsed.cors_rules[0];
+        assert_eq!(rule.id, Some("web".to_string()));
+        assert_eq!(rule.allowed_origins, vec!["https://example.com", "https://www.example.com"]);
+        assert_eq!(rule.allowed_methods, vec!["GET", "PUT"]);
+        assert_eq!(rule.allowed_headers, vec!["*"]);
+        assert_eq!(rule.expose_headers, vec!["ETag"]);
+        assert_eq!(rule.max_age_seconds, 3000);
+        assert_eq!(parsed.cors_rules[1].allowed_origins, vec!["*"]);
+        assert_eq!(parsed.cors_rules[1].max_age_seconds, 0);
+    }
+}
@@ -18,6 +18,9 @@
 //! The crate Url is required and used to build and extract portions of the Url as needed by
 //! the library. See the README.md and/or src/main.rs for an example of how to use the library.
 
+use std::path::PathBuf;
+use std::time::Duration;
+
 use url::Url;
 use aws::common::region::Region;
 
@@ -54,6 +57,71 @@ pub struct Endpoint {
     /// new load balancer and VIP range are unable to represent a virtual bucket due to configs
     /// in Ceph RGW DNS.
     pub is_bucket_virtual: bool,
+    /// `options` selects the dual-stack, FIPS and accelerate variants of the AWS endpoints. They
+    /// are only used when the Url is resolved from the Region (see `Endpoint::with_options`) and
+    /// when requests are redirected to the region of a bucket.
+    pub options: EndpointOptions,
+    /// `connection` holds the timeouts and the connection pool settings of the hyper client that
+    /// `S3Client::new` builds. Change them before creating the client.
+    pub connection: ConnectionOptions,
+    /// `tls` adds CA certificates, a client certificate and certificate pins to the TLS
+    /// connections of the hyper client that `S3Client::try_new` builds (also through a proxy).
+    pub tls: TlsOptions,
+}
+
+/// Variants of the AWS S3 endpoints. All are off by default.
+#[derive(Debug, Default, Clone, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct EndpointOptions {
+    /// s3.dualstack.{region}.amazonaws.com - Accepts IPv4 and IPv6.
+    pub dual_stack: bool,
+    /// s3-fips.{region}.amazonaws.com - FIPS 140-2 validated endpoints. Ignored with `accelerate`.
+    pub fips: bool,
+    /// s3-accelerate.amazonaws.com - Transfer Acceleration. It must be enabled on the bucket and
+    /// only works with virtual buckets.
+    pub accelerate: bool,
+}
+
+/// Timeouts and connection pool settings of the HTTP client. A timeout returns an `S3Error` with
+/// the `Timeout` kind. `None` disables a timeout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionOptions {
+    /// Time allowed to open the TCP connection (to the proxy if there is one).
+    pub connect_timeout: Option<Duration>,
+    /// Time allowed for a single read of the socket. A stalled gateway fails after this.
+    pub read_timeout: Option<Duration>,
+    /// Time allowed for a single write to the socket.
+    pub write_timeout: Option<Duration>,
+    /// Time allowed for a whole request. It is checked between the reads of a streamed request
+    /// body and of the response body so a single socket operation can still run for up to the
+    /// read or write timeout past it. It is off by default since large objects take as long as
+    /// they take.
+    pub request_timeout: Option<Duration>,
+    /// Idle connections kept open per host for reuse (keep-alive). 0 opens a new connection for
+    /// every request.
+    pub max_idle_connections: usize,
+    /// How long an idle connection is reused. S3 closes idle connections after about 20 seconds.
+    pub idle_timeout: Option<Duration>,
+}
+
+/// TLS settings for endpoints that don't use a public CA (on-prem Ceph RGW for example) or that
+/// require mutual TLS. The system CA certificates are always trusted. All paths are PEM files.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TlsOptions {
+    /// File with CA certificates trusted in addition to the system ones.
+    pub ca_file: Option<PathBuf>,
+    /// Directory of CA certificate files trusted in addition to the system ones. Every file in
+    /// it is loaded.
+    pub ca_dir: Option<PathBuf>,
+    /// Client certificate (chain) presented to endpoints that require mutual TLS.
+    pub client_cert: Option<PathBuf>,
+    /// Private key of the client certificate. If None the key is read from `client_cert`.
+    pub client_key: Option<PathBuf>,
+    /// SHA-256 fingerprints (hex, with or without ':') of the accepted server certificates. When
+    /// not empty the certificate of the endpoint must also match one of them.
+    pub pinned_sha256: Vec<String>,
+    /// Accepts any certificate and host name. Only for lab use since it allows anyone to
+    /// intercept the requests.
+    pub insecure: bool,
 }
 
 /// Required to specify which type of API Signature to use. AWS defaults to using V4 by default.
@@ -65,6 +133,19 @@ pub enum Signature {
     V4,
 }
 
+impl Default for ConnectionOptions {
+    fn default() -> ConnectionOptions {
+        ConnectionOptions {
+            connect_timeout: Some(Duration::from_secs(10)),
+            read_timeout: Some(Duration::from_secs(60)),
+            write_timeout: Some(Duration::from_secs(60)),
+            request_timeout: None,
+            max_idle_connections: 0,
+            idle_timeout: Some(Duration::from_secs(15)),
+        }
+    }
+}
+
 impl Endpoint {
     /// Endpoint::new accepts Region, Signature, an optional Url and an optional proxy Url:port.
     pub fn new(region: Region,
@@ -73,13 +154,39 @@ impl Endpoint {
                proxy: Option<Url>,
                user_agent: Option<String>,
                is_bucket_virtual: Option<bool>) -> Self {
+        let endpoint = default_endpoint(&region, endpoint, &EndpointOptions::default());
+        Endpoint {
+            region: region,
+            signature: signature,
+            endpoint: endpoint,
+            proxy: proxy,
+            user_agent: user_agent,
+            is_bucket_virtual: is_bucket_virtual.unwrap_or(true),
+            options: EndpointOptions::default(),
+            connection: ConnectionOptions::default(),
+            tls: TlsOptions::default(),
+        }
+    }
+
+    /// Endpoint::with_options is like `Endpoint::new` without a Url. The Url is resolved from the
+    /// Region and the `EndpointOptions` (dual-stack, FIPS and accelerate).
+    pub fn with_options(region: Region,
+                        signature: Signature,
+                        options: EndpointOptions,
+                        proxy: Option<Url>,
+                        user_agent: Option<String>,
+                        is_bucket_virtual: Option<bool>) -> Self {
+        let endpoint = default_endpoint(&region, None, &options);
         Endpoint {
             region: region,
             signature: signature,
-            endpoint: default_endpoint(region, endpoint),
+            endpoint: endpoint,
             proxy: proxy,
             user_agent: user_agent,
             is_bucket_virtual: is_bucket_virtual.unwrap_or(true),
+            options: options,
+            connection: ConnectionOptions::default(),
+            tls: TlsOptions::default(),
         }
     }
 
@@ -90,23 +197,140 @@ impl Endpoint {
             Some(ref url) => Some(url.host_str().unwrap().to_string()),
         }
     }
+
+    /// Returns true if the endpoint is an AWS S3 endpoint and not a third party service.
+    pub fn is_aws(&self) -> bool {
+        match self.hostname() {
+            Some(hostname) => hostname.ends_with(".amazonaws.com") || hostname.ends_with(".amazonaws.com.cn"),
+            None => false,
+        }
+    }
+
+    /// Returns true if requests for the bucket use a virtual bucket hostname ({bucket}.{hostname})
+    /// and false if they fall back to path style. Bucket names that aren't valid DNS names can't be
+    /// virtual and neither can names with '.' over https since they don't match the certificate.
+    pub fn use_virtual_host(&self, bucket: &str) -> bool {
+        if !self.is_bucket_virtual || !is_dns_compatible_bucket(bucket) {
+            return false;
+        }
+        let is_https = match self.endpoint {
+            Some(ref url) => url.scheme() == "https",
+            None => true,
+        };
+        !(is_https && bucket.contains("."))
+    }
+
+    /// Returns the hostname requests for the bucket are sent to on the given endpoint hostname.
+    pub fn bucket_hostname(&self, bucket: &str, hostname: &str) -> String {
+        if self.use_virtual_host(bucket) {
+            format!("{}.{}", bucket, hostname)
+        } else {
+            hostname.to_string()
+        }
+    }
+}
+
+/// Returns true if the bucket name can be used as a DNS label (lowercase letters, digits, '-' and
+/// '.', 3 to 63 characters, no empty labels and not an IP address).
+pub fn is_dns_compatible_bucket(bucket: &str) -> bool {
+    if bucket.len() < 3 || bucket.len() > 63 {
+        return false;
+    }
+    let valid_chars = bucket.chars().all(|c| (c >= 'a' && c <= 'z') || c.is_digit(10) || c == '-' || c == '.');
+    if !valid_chars {
+        return false;
+    }
+    let valid_labels = bucket.split('.').all(|label| {
+        !label.is_empty() && !label.starts_with('-') && !label.ends_with('-')
+    });
+    let is_ip_address = bucket.split('.').count() == 4 &&
+                        bucket.split('.').all(|label| label.parse::<u8>().is_ok());
+    valid_labels && !is_ip_address
+}
+
+/// Returns the AWS S3 hostname (without the bucket) of a given Region and options. `Custom`
+/// regions return the hostname of their endpoint.
+pub fn s3_hostname(region: &Region, options: &EndpointOptions) -> String {
+    if let Some(hostname) = region.custom_hostname() {
+        return hostname;
+    }
+
+    if options.accelerate {
+        return format!("s3-accelerate{}.amazonaws.com", if options.dual_stack {".dualstack"} else {""});
+    }
+
+    let domain = if region.is_china() {"amazonaws.com.cn"} else {"amazonaws.com"};
+    match (options.fips, options.dual_stack) {
+        (false, false) if *region == Region::UsEast1 => "s3.amazonaws.com".to_string(),
+        (false, false) => format!("s3.{}.{}", region, domain),
+        (false, true) => format!("s3.dualstack.{}.{}", region, domain),
+        (true, false) => format!("s3-fips.{}.{}", region, domain),
+        (true, true) => format!("s3-fips.dualstack.{}.{}", region, domain),
+    }
 }
 
 // This creates the default endpoint to be used on initial create if endpoint is None
-fn default_endpoint(region: Region, endpoint: Option<Url>) -> Option<Url> {
+fn default_endpoint(region: &Region, endpoint: Option<Url>, options: &EndpointOptions) -> Option<Url> {
     let final_endpoint: Url;
     match endpoint {
         Some(url) => final_endpoint = url,
         None => {
             // NOTE: Must include the correct scheme (http or https)
-            let endpoint: String = match region {
-                Region::UsEast1 => "https://s3.amazonaws.com".to_string(),
-                Region::CnNorth1 => format!("https://s3.{}.amazonaws.com.cn", region),
-                _ => format!("https://s3.amazonaws.com"),
+            let endpoint = match *region {
+                Region::Custom { ref endpoint, .. } if endpoint.contains("://") => endpoint.to_string(),
+                Region::Custom { ref endpoint, .. } => format!("https://{}", endpoint),
+                _ => format!("https://{}", s3_hostname(region, options)),
+            };
+            final_endpoint = match Url::parse(&endpoint) {
+                Ok(url) => url,
+                Err(_) => return None,
             };
-            final_endpoint = Url::parse(&endpoint).unwrap();
         },
     };
 
     Some(final_endpoint)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use url::Url;
+
+    use aws::common::region::Region;
+
+    #[test]
+    fn endpoint_resolution() {
+        let mut options = EndpointOptions::default();
+        assert_eq!(s3_hostname(&Region::UsEast1, &options), "s3.amazonaws.com");
+        assert_eq!(s3_hostname(&Region::EuWest2, &options), "s3.eu-west-2.amazonaws.com");
+        assert_eq!(s3_hostname(&Region::CnNorthwest1, &options), "s3.cn-northwest-1.amazonaws.com.cn");
+
+        options.dual_stack = true;
+        assert_eq!(s3_hostname(&Region::UsEast1, &options), "s3.dualstack.us-east-1.amazonaws.com");
+        options.fips = true;
+        assert_eq!(s3_hostname(&Region::UsGovWest1, &options), "s3-fips.dualstack.us-gov-west-1.amazonaws.com");
+        options.accelerate = true;
+        assert_eq!(s3_hostname(&Region::UsWest2, &options), "s3-accelerate.dualstack.amazonaws.com");
+
+        let endpoint = Endpoint::with_options(Region::UsWest2, Signature::V4, options, None, None, None);
+        assert_eq!(endpoint.hostname(), Some("s3-accelerate.dualstack.amazonaws.com".to_string()));
+    }
+
+    #[test]
+    fn path_style_fallback() {
+        let endpoint = Endpoint::new(Region::UsEast1, Signature::V4, None, None, None, None);
+        assert_eq!(endpoint.bucket_hostname("bucket", "s3.amazonaws.com"), "bucket.s3.amazonaws.com");
+        assert_eq!(endpoint.bucket_hostname("my.bucket", "s3.amazonaws.com"), "s3.amazonaws.com");
+        assert_eq!(endpoint.bucket_hostname("My_Bucket", "s3.amazonaws.com"), "s3.amazonaws.com");
+        assert!(!endpoint.use_virtual_host("192.168.1.1"));
+
+        let endpoint = Endpoint::new(Region::UsEast1,
+                                     Signature::V2,
+                                     Some(Url::parse("http://rgw.example.com").unwrap()),
+                                     None,
+                                     None,
+                                     None);
+        assert!(endpoint.use_virtual_host("my.bucket"));
+    }
+}
@@ -14,21 +14,25 @@
 //
 
 #![allow(unused_variables)]
+use xml::escape::escape_str_pcdata;
+
 use aws::common::params::{Params, ServiceParams};
 use aws::common::xmlutil::*;
 use aws::common::common::*;
 use aws::s3::writeparse::*;
 use aws::s3::acl::*;
 
-pub type GrantRead = String;
+pub type HeaderGrantees = Vec<HeaderGrantee>;
+
+pub type GrantRead = HeaderGrantees;
 
-pub type GrantWrite = String;
+pub type GrantWrite = HeaderGrantees;
 
-pub type GrantWriteACP = String;
+pub type GrantWriteACP = HeaderGrantees;
 
-pub type GrantReadACP = String;
+pub type GrantReadACP = HeaderGrantees;
 
-pub type GrantFullControl = String;
+pub type GrantFullControl = HeaderGrantees;
 
 pub type Grants = Vec<Grant>;
 
@@ -88,6 +92,18 @@ pub struct TargetGrantParser;
 /// Write `TargetGrant` contents to a `SignedRequest`
 pub struct TargetGrantWriter;
 
+/// A grantee of one of the `x-amz-grant-*` headers. A header lists one or more grantees
+/// separated by commas (e.g. `id="1234", emailAddress="user@example.com"`).
+#[derive(Debug, Clone, PartialEq, RustcDecodable, RustcEncodable)]
+pub enum HeaderGrantee {
+    /// The canonical user ID of an AWS account.
+    Id(ID),
+    /// Email address of an AWS account.
+    EmailAddress(EmailAddress),
+    /// URI of a predefined group (e.g. `http://acs.amazonaws.com/groups/global/AllUsers`).
+    Uri(URI),
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct TargetGrant {
@@ -121,6 +137,70 @@ pub struct Grantee {
 
 // Impls below...
 
+/// Formats the grantees as the value of an `x-amz-grant-*` header.
+pub fn grant_header_value(grantees: &HeaderGrantees) -> String {
+    grantees.iter()
+        .map(|grantee| {
+            match *grantee {
+                HeaderGrantee::Id(ref id) => format!("id=\"{}\"", id),
+                HeaderGrantee::EmailAddress(ref email) => format!("emailAddress=\"{}\"", email),
+                HeaderGrantee::Uri(ref uri) => format!("uri=\"{}\"", uri),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Writes out a `Grantee` element for XML request bodies. The grantee type is taken from
+/// `some_type` or, if empty, from the first of `id`, `email_address` or `uri` that is set.
+pub fn grantee_xml(grantee: &Grantee) -> String {
+    let grantee_type = if !grantee.some_type.is_empty() {
+        grantee.some_type.clone()
+    } else if grantee.id.is_some() {
+        "CanonicalUser".to_string()
+    } else if grantee.email_address.is_some() {
+        "AmazonCustomerByEmail".to_string()
+    } else {
+        "Group".to_string()
+    };
+
+    let mut xml = format!("<Grantee xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xsi:type=\"{}\">",
+                          grantee_type);
+    if let Some(ref id) = grantee.id {
+        xml.push_str(&format!("<ID>{}</ID>", escape_str_pcdata(id)));
+    }
+    if let Some(ref display_name) = grantee.display_name {
+        xml.push_str(&format!("<DisplayName>{}</DisplayName>", escape_str_pcdata(display_name)));
+    }
+    if let Some(ref email) = grantee.email_address {
+        xml.push_str(&format!("<EmailAddress>{}</EmailAddress>", escape_str_pcdata(email)));
+    }
+    if let Some(ref uri) = grantee.uri {
+        xml.push_str(&format!("<URI>{}</URI>", escape_str_pcdata(uri)));
+    }
+    xml.push_str("</Grantee>");
+    xml
+}
+
+/// Parses the value of an `x-amz-grant-*` header. Unknown grantee types are skipped.
+pub fn parse_grant_header(value: &str) -> HeaderGrantees {
+    let mut grantees = Vec::new();
+    for item in value.split(',') {
+        let mut parts = item.trim().splitn(2, '=');
+        let (key, val) = match (parts.next(), parts.next()) {
+            (Some(key), Some(val)) => (key.trim(), val.trim().trim_matches('"').to_string()),
+            _ => continue,
+        };
+        match key {
+            "id" => grantees.push(HeaderGrantee::Id(val)),
+            "emailAddress" => grantees.push(HeaderGrantee::EmailAddress(val)),
+            "uri" => grantees.push(HeaderGrantee::Uri(val)),
+            _ => {},
+        }
+    }
+    grantees
+}
+
 impl GrantsParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T)
         -> Result<Grants, XmlParseError> {
@@ -149,13 +229,13 @@ impl GrantReadACPParser {
         try!(start_element(tag_name, stack));
         let obj = try!(characters(stack));
         try!(end_element(tag_name, stack));
-        Ok(obj)
+        Ok(parse_grant_header(&obj))
     }
 }
 
 impl GrantReadACPWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &GrantReadACP) {
-        params.put(name, obj);
+        params.put(name, &grant_header_value(obj));
     }
 }
 
@@ -253,13 +333,13 @@ impl GrantReadParser {
         try!(start_element(tag_name, stack));
         let obj = try!(characters(stack));
         try!(end_element(tag_name, stack));
-        Ok(obj)
+        Ok(parse_grant_header(&obj))
     }
 }
 
 impl GrantReadWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &GrantRead) {
-        params.put(name, obj);
+        params.put(name, &grant_header_value(obj));
     }
 }
 
@@ -269,13 +349,13 @@ impl GrantWriteACPParser {
         try!(start_element(tag_name, stack));
         let obj = try!(characters(stack));
         try!(end_element(tag_name, stack));
-        Ok(obj)
+        Ok(parse_grant_header(&obj))
     }
 }
 
 impl GrantWriteACPWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &GrantWriteACP) {
-        params.put(name, obj);
+        params.put(name, &grant_header_value(obj));
     }
 }
 
@@ -284,13 +364,13 @@ impl GrantWriteParser {
         try!(start_element(tag_name, stack));
         let obj = try!(characters(stack));
         try!(end_element(tag_name, stack));
-        Ok(obj)
+        Ok(parse_grant_header(&obj))
     }
 }
 
 impl GrantWriteWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &GrantWrite) {
-        params.put(name, obj);
+        params.put(name, &grant_header_value(obj));
     }
 }
 
@@ -299,13 +379,13 @@ impl GrantFullControlParser {
         try!(start_element(tag_name, stack));
         let obj = try!(characters(stack));
         try!(end_element(tag_name, stack));
-        Ok(obj)
+        Ok(parse_grant_header(&obj))
     }
 }
 
 impl GrantFullControlWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &GrantFullControl) {
-        params.put(name, obj);
+        params.put(name, &grant_header_value(obj));
     }
 }
 
@@ -342,3 +422,19 @@ impl TargetGrantWriter {
                                                  &obj.permission);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grant_header_round_trip() {
+        let grantees = vec![HeaderGrantee::Id("1234".to_string()),
+                            HeaderGrantee::EmailAddress("user@example.com".to_string()),
+                            HeaderGrantee::Uri("http://acs.amazonaws.com/groups/global/AllUsers".to_string())];
+        let value = grant_header_value(&grantees);
+        assert_eq!(value,
+                   "id=\"1234\", emailAddress=\"user@example.com\", uri=\"http://acs.amazonaws.com/groups/global/AllUsers\"");
+        assert_eq!(parse_grant_header(&value), grantees);
+    }
+}
@@ -14,7 +14,11 @@
 //
 
 #![allow(unused_variables)]
+use std::ascii::AsciiExt;
+
+use aws::common::common::{HostId, RequestId};
 use aws::common::params::{Params, ServiceParams};
+use aws::common::request::HttpResponse;
 use aws::common::xmlutil::*;
 
 pub type ContentRange = String;
@@ -39,6 +43,20 @@ pub type AcceptRanges = String;
 
 pub type MissingHeaderName = String;
 
+/// The ids S3 (and Ceph RGW) return with every response. AWS support asks for both when
+/// tracing a request and RGW logs them with the request.
+///
+/// Outputs carry them in their `response_metadata` field. `head_bucket` returns `()` so its ids
+/// are not available on success. For a failed request S3 returns the request id in the error document (`S3Error.aws.request_id`).
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, RustcDecodable, RustcEncodable)]
+pub struct ResponseMetadata {
+    /// Unique id of the request (x-amz-request-id).
+    pub request_id: RequestId,
+    /// Id of the host or RGW zone group that handled the request (x-amz-id-2).
+    pub host_id: HostId,
+}
+
 /// Parse `ContentRange` from XML
 pub struct ContentRangeParser;
 
@@ -277,3 +295,43 @@ impl MissingHeaderNameWriter {
         params.put(name, obj);
     }
 }
+
+impl ResponseMetadata {
+    /// Reads the request ids from the headers of a response. Header names are compared without
+    /// case since proxies and RGW don't always keep the case S3 uses.
+    pub fn from_response(response: &HttpResponse) -> ResponseMetadata {
+        let mut metadata = ResponseMetadata::default();
+        for (name, value) in response.headers.iter() {
+            if name.eq_ignore_ascii_case("x-amz-request-id") {
+                metadata.request_id = value.to_string();
+            } else if name.eq_ignore_ascii_case("x-amz-id-2") {
+                metadata.host_id = value.to_string();
+            }
+        }
+        metadata
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use aws::common::request::HttpResponse;
+    use super::*;
+
+    #[test]
+    fn response_metadata_from_headers() {
+        let mut headers = HashMap::new();
+        headers.insert("X-Amz-Request-Id".to_string(), "4442587FB7D0A2F9".to_string());
+        headers.insert("x-amz-id-2".to_string(), "vlR7PnpV2Ce81l0PRw6jlUpck7Jo5ZsQjryTjKlc5aLWGVHPZLj5NeC6qMa0emYBDXOo6QBU0Wo=".to_string());
+        let response = HttpResponse {
+            status: 200,
+            body: Vec::new(),
+            headers: headers,
+        };
+
+        let metadata = ResponseMetadata::from_response(&response);
+        assert_eq!(metadata.request_id, "4442587FB7D0A2F9");
+        assert!(metadata.host_id.starts_with("vlR7PnpV2Ce81l0"));
+    }
+}
@@ -17,8 +17,12 @@ pub mod s3client;
 pub mod bucket;
 pub mod object;
 pub mod policy;
+pub mod postpolicy;
 pub mod writeparse;
 pub mod acl;
 pub mod grant;
 pub mod header;
 pub mod admin;
+pub mod paginator;
+pub mod sse;
+pub mod retry;
@@ -24,7 +24,11 @@
 use std::str::FromStr;
 use std::str;
 
+use xml::escape::escape_str_pcdata;
+
+use aws::common::body::StreamingBody;
 use aws::common::params::{Params, ServiceParams};
+use aws::common::signature::byte_serialize;
 use aws::common::xmlutil::*;
 use aws::common::common::*;
 use aws::errors::http::*;
@@ -33,10 +37,15 @@ use aws::s3::header::*;
 use aws::s3::bucket::*;
 use aws::s3::acl::*;
 use aws::s3::grant::*;
+use aws::s3::sse::CustomerKey;
 use aws::s3::writeparse::*;
 
 pub type TagSet = Vec<Tag>;
 
+/// Specifies whether the object tags are copied from the source object or replaced with the
+/// tags provided in the request. Valid values are COPY and REPLACE.
+pub type TaggingDirective = String;
+
 pub type Parts = Vec<Part>;
 
 pub type PartNumber = i32;
@@ -71,6 +80,8 @@ pub type DeleteMarkerVersionId = String;
 
 pub type DeletedObjects = Vec<DeletedObject>;
 
+pub type DeleteErrors = Vec<S3ClientError>;
+
 pub type ObjectStorageClass = String;
 
 pub type DeleteMarkers = Vec<DeleteMarkerEntry>;
@@ -92,6 +103,9 @@ pub struct TagSetParser;
 /// Write `TagSet` contents to a `SignedRequest`
 pub struct TagSetWriter;
 
+/// Parse `Tagging` from XML
+pub struct TaggingParser;
+
 /// Parse `PartNumber` from XML
 pub struct PartNumberParser;
 
@@ -305,6 +319,12 @@ pub struct DeletedObjectsParser;
 /// Write `DeletedObjects` contents to a `SignedRequest`
 pub struct DeletedObjectsWriter;
 
+/// Parse `DeleteErrors` from XML
+pub struct DeleteErrorsParser;
+
+/// Write `DeleteErrors` contents to a `SignedRequest`
+pub struct DeleteErrorsWriter;
+
 /// Parse `DeleteObjectOutput` from XML
 pub struct DeleteObjectOutputParser;
 
@@ -509,9 +529,6 @@ pub struct GetObjectOutput {
     pub metadata: Metadata,
     /// Object data.
     pub body: Body,
-    pub body_buffer: Vec<u8>,
-    // Used only for get_body function to return the correct slice of data
-    pub is_body: bool,
     pub accept_ranges: AcceptRanges,
     /// If the bucket is configured as a website, redirects requests for this object
     /// to another object in the same bucket or to an external URL. Amazon S3 stores
@@ -556,12 +573,14 @@ pub struct GetObjectOutput {
     /// requested, the response will include this header to provide round trip message
     /// integrity verification of the customer-provided encryption key.
     pub sse_customer_key_md5: SSECustomerKeyMD5,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct RestoreObjectOutput {
     pub request_charged: RequestCharged,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
@@ -604,14 +623,18 @@ pub struct DeleteObjectOutput {
     /// Specifies whether the versioned object that was permanently deleted was (true)
     /// or was not (false) a delete marker.
     pub delete_marker: DeleteMarker,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct DeleteObjectsOutput {
+    /// Objects that were successfully deleted. Empty when the request was sent in quiet mode.
     pub deleted: DeletedObjects,
-    pub errors: Errors,
+    /// Objects that could not be deleted along with the reason (`Code` and `Message`).
+    pub errors: DeleteErrors,
     pub request_charged: RequestCharged,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
@@ -648,6 +671,7 @@ pub struct PutObjectOutput {
     /// If present, specifies the ID of the AWS Key Management Service (KMS) master
     /// encryption key that was used for the object.
     pub ssekms_key_id: SSEKMSKeyId,
+    pub response_metadata: ResponseMetadata,
 }
 
 /// Container for specifying the configuration when you want Amazon S3 to publish
@@ -660,6 +684,8 @@ pub struct TopicConfiguration {
     /// events of specified type.
     pub topic_arn: TopicArn,
     pub events: EventList,
+    /// Only objects with key names matching the filter rules cause a notification.
+    pub filter: Option<NotificationConfigurationFilter>,
 }
 
 /// Container for specifying an configuration when you want Amazon S3 to publish
@@ -672,6 +698,8 @@ pub struct QueueConfiguration {
     /// events of specified type.
     pub queue_arn: QueueArn,
     pub events: EventList,
+    /// Only objects with key names matching the filter rules cause a notification.
+    pub filter: Option<NotificationConfigurationFilter>,
 }
 
 //#[derive(Debug, Default)]
@@ -687,6 +715,8 @@ pub struct ObjectIdentifier {
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct MultipartUploadCreateRequest {
     pub request_payer: Option<RequestPayer>,
+    /// Tags set on the object once the upload completes. Sent as the x-amz-tagging header.
+    pub tagging: Option<TagSet>,
     /// Specifies what content encodings have been applied to the object and thus what
     /// decoding mechanisms must be applied to obtain the media-type referenced by the
     /// Content-Type header field.
@@ -715,6 +745,9 @@ pub struct MultipartUploadCreateRequest {
     /// appropriate for use with the algorithm specified in the x-amz-server-side-
     /// encryption-customer-algorithm header.
     pub sse_customer_key: Option<SSECustomerKey>,
+    /// Customer provided key (SSE-C). Takes precedence over `sse_customer_algorithm`,
+    /// `sse_customer_key` and `sse_customer_key_md5` which must already be encoded.
+    pub sse_customer: Option<CustomerKey>,
     /// If the bucket is configured as a website, redirects requests for this object
     /// to another object in the same bucket or to an external URL. Amazon S3 stores
     /// the value of this header in the object metadata.
@@ -777,6 +810,8 @@ pub struct MultipartUploadAbortRequest {
 #[derive(Debug, Default, RustcEncodable)]
 pub struct MultipartUploadPartRequest <'a> {
     pub body: Option<&'a [u8]>,
+    /// Part data streamed from a `Read` source. Takes precedence over `body`.
+    pub body_stream: Option<StreamingBody>,
     /// Specifies the algorithm to use to when encrypting the object (e.g., AES256).
     pub sse_customer_algorithm: Option<SSECustomerAlgorithm>,
     pub request_payer: Option<RequestPayer>,
@@ -792,6 +827,9 @@ pub struct MultipartUploadPartRequest <'a> {
     /// encryption-customer-algorithm header. This must be the same encryption key
     /// specified in the initiate multipart upload request.
     pub sse_customer_key: Option<SSECustomerKey>,
+    /// Customer provided key (SSE-C). Takes precedence over `sse_customer_algorithm`,
+    /// `sse_customer_key` and `sse_customer_key_md5` which must already be encoded.
+    pub sse_customer: Option<CustomerKey>,
     /// Upload ID identifying the multipart upload whose part is being uploaded.
     pub upload_id: MultipartUploadId,
     pub key: ObjectKey,
@@ -843,9 +881,10 @@ pub struct MultipartUploadListPartsOutput {
     pub is_truncated: IsTruncated,
     /// Part number after which listing begins.
     pub part_number_marker: PartNumberMarker,
+    pub response_metadata: ResponseMetadata,
 }
 
-#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+#[derive(Debug, Default, Clone, RustcDecodable, RustcEncodable)]
 pub struct MultipartUploadListPartsRequest {
     pub request_payer: Option<RequestPayer>,
     pub bucket: BucketName,
@@ -859,7 +898,7 @@ pub struct MultipartUploadListPartsRequest {
     pub part_number_marker: Option<PartNumberMarker>,
 }
 
-#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+#[derive(Debug, Default, Clone, RustcDecodable, RustcEncodable)]
 pub struct MultipartUploadListRequest {
     /// Together with key-marker, specifies the multipart upload after which listing
     /// should begin. If key-marker is not specified, the upload-id-marker parameter
@@ -890,6 +929,8 @@ pub struct MultipartUploadListRequest {
 #[derive(Debug, Default, RustcEncodable)]
 pub struct PutObjectRequest<'a> {
     pub request_payer: Option<RequestPayer>,
+    /// Tags set on the object. Sent as the x-amz-tagging header.
+    pub tagging: Option<TagSet>,
     /// Specifies what content encodings have been applied to the object and thus what
     /// decoding mechanisms must be applied to obtain the media-type referenced by the
     /// Content-Type header field.
@@ -914,12 +955,17 @@ pub struct PutObjectRequest<'a> {
     pub metadata: Option<Metadata>,
     /// Object data.
     pub body: Option<&'a [u8]>,
+    /// Object data streamed from a `Read` source. Takes precedence over `body`.
+    pub body_stream: Option<StreamingBody>,
     /// Specifies the customer-provided encryption key for Amazon S3 to use in
     /// encrypting data. This value is used to store the object and then it is
     /// discarded; Amazon does not store the encryption key. The key must be
     /// appropriate for use with the algorithm specified in the x-amz-server-side-
     /// encryption-customer-algorithm header.
     pub sse_customer_key: Option<SSECustomerKey>,
+    /// Customer provided key (SSE-C). Takes precedence over `sse_customer_algorithm`,
+    /// `sse_customer_key` and `sse_customer_key_md5` which must already be encoded.
+    pub sse_customer: Option<CustomerKey>,
     /// If the bucket is configured as a website, redirects requests for this object
     /// to another object in the same bucket or to an external URL. Amazon S3 stores
     /// the value of this header in the object metadata.
@@ -979,6 +1025,9 @@ pub struct GetObjectRequest {
     /// appropriate for use with the algorithm specified in the x-amz-server-side-
     /// encryption-customer-algorithm header.
     pub sse_customer_key: Option<SSECustomerKey>,
+    /// Customer provided key (SSE-C). Takes precedence over `sse_customer_algorithm`,
+    /// `sse_customer_key` and `sse_customer_key_md5` which must already be encoded.
+    pub sse_customer: Option<CustomerKey>,
     pub bucket: BucketName,
     /// Return the object only if its entity tag (ETag) is different from the one
     /// specified, otherwise return a 304 (not modified).
@@ -1083,10 +1132,12 @@ pub struct LambdaFunctionConfiguration {
     pub lambda_function_arn: LambdaFunctionArn,
     pub id: Option<NotificationId>,
     pub events: EventList,
+    /// Only objects with key names matching the filter rules cause a notification.
+    pub filter: Option<NotificationConfigurationFilter>,
 }
 
 //#[derive(Debug, Default)]
-#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+#[derive(Debug, Default, Clone, PartialEq, RustcDecodable, RustcEncodable)]
 pub struct Tag {
     /// Value of the tag.
     pub value: Value,
@@ -1094,6 +1145,68 @@ pub struct Tag {
     pub key: ObjectKey,
 }
 
+/// The `TagSet` of a bucket or object.
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, RustcDecodable, RustcEncodable)]
+pub struct Tagging {
+    pub tag_set: TagSet,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutObjectTaggingRequest {
+    pub bucket: BucketName,
+    pub key: ObjectKey,
+    /// VersionId of the object the tags are added to. Defaults to the current version.
+    pub version_id: Option<ObjectVersionId>,
+    /// Replaces any tags already on the object.
+    pub tagging: Tagging,
+    pub content_md5: Option<ContentMD5>,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct PutObjectTaggingOutput {
+    /// VersionId of the object the tags were added to.
+    pub version_id: ObjectVersionId,
+    pub response_metadata: ResponseMetadata,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetObjectTaggingRequest {
+    pub bucket: BucketName,
+    pub key: ObjectKey,
+    /// VersionId of the object the tags are returned for. Defaults to the current version.
+    pub version_id: Option<ObjectVersionId>,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetObjectTaggingOutput {
+    /// VersionId of the object the tags belong to.
+    pub version_id: ObjectVersionId,
+    pub tag_set: TagSet,
+    pub response_metadata: ResponseMetadata,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct DeleteObjectTaggingRequest {
+    pub bucket: BucketName,
+    pub key: ObjectKey,
+    /// VersionId of the object the tags are removed from. Defaults to the current version.
+    pub version_id: Option<ObjectVersionId>,
+}
+
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct DeleteObjectTaggingOutput {
+    /// VersionId of the object the tags were removed from.
+    pub version_id: ObjectVersionId,
+    pub response_metadata: ResponseMetadata,
+}
+
 //#[derive(Debug, Default)]
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct CopyObjectResult {
@@ -1132,6 +1245,7 @@ pub struct MultipartUploadListOutput {
     /// the number of multipart uploads exceeds the limit allowed or specified by max
     /// uploads.
     pub is_truncated: IsTruncated,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
@@ -1158,6 +1272,7 @@ pub struct MultipartUploadCreateOutput {
     /// If present, specifies the ID of the AWS Key Management Service (KMS) master
     /// encryption key that was used for the object.
     pub ssekms_key_id: SSEKMSKeyId,
+    pub response_metadata: ResponseMetadata,
 }
 
 //#[derive(Debug, Default)]
@@ -1180,11 +1295,13 @@ pub struct MultipartUploadCompleteOutput {
     /// If the object expiration is configured, this will contain the expiration date
     /// (expiry-date) and rule ID (rule-id). The value of rule-id is URL encoded.
     pub expiration: Expiration,
+    pub response_metadata: ResponseMetadata,
 }
 
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct MultipartUploadAbortOutput {
     pub request_charged: RequestCharged,
+    pub response_metadata: ResponseMetadata,
 }
 
 /// Specifies when noncurrent object versions expire. Upon expiration, Amazon S3
@@ -1222,7 +1339,7 @@ pub struct ReplicationRule {
 /// prefixes such [0-f][0-f][0-f][0-f]<whatever date> could be used to do a 4 character hex hash
 /// that prefixes your object key which also included a date. This prefix would allow for sort correctly.
 /// http://docs.aws.amazon.com/AmazonS3/latest/dev/request-rate-perf-considerations.html
-#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+#[derive(Debug, Default, Clone, RustcDecodable, RustcEncodable)]
 pub struct ListObjectsRequest {
     /// Required. Name of bucket.
     pub bucket: BucketName,
@@ -1236,9 +1353,18 @@ pub struct ListObjectsRequest {
     pub max_keys: Option<MaxKeys>,
     /// A delimiter is a character you use to group keys.
     pub delimiter: Option<Delimiter>,
-    /// Specifies the key to start with when listing objects in a bucket.
+    /// Version 1. Specifies the key to start with when listing objects in a bucket.
     pub marker: Option<Marker>,
+    /// Requests Amazon S3 to encode the object keys in the response. The only valid value is `url`.
     pub encoding_type: Option<EncodingType>,
+    /// Version 2. Continues a truncated listing using the `next_continuation_token` returned
+    /// by the previous response.
+    pub continuation_token: Option<ContinuationToken>,
+    /// Version 2. Starts listing after this key. Ignored if `continuation_token` is set.
+    pub start_after: Option<StartAfter>,
+    /// Version 2. The owner is not returned with each key by default. Set to `Some(true)` to
+    /// include it.
+    pub fetch_owner: Option<bool>,
 }
 
 /// ListObjectsOutput contains the list of objects and their associated metadata for a given
@@ -1282,9 +1408,10 @@ pub struct ListObjectsOutput {
     pub key_count: KeyCount,
     /// Version 2. Is included with the response if sent with the request.
     pub start_after: StartAfter,
+    pub response_metadata: ResponseMetadata,
 }
 
-#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+#[derive(Debug, Default, Clone, RustcDecodable, RustcEncodable)]
 pub struct ListObjectVersionsRequest {
     pub bucket: BucketName,
     /// Limits the response to keys that begin with the specified prefix.
@@ -1330,6 +1457,17 @@ pub struct ListVersionsResult {
     pub encoding_type: EncodingType,
     pub version_id_marker: VersionIdMarker,
     pub common_prefixes: CommonPrefixList,
+    /// The kind of each `Version` and `DeleteMarker` entry in the order S3 returned them. Used
+    /// to merge `versions` and `delete_markers` back into that order.
+    pub entry_order: Vec<VersionEntryKind>,
+    pub response_metadata: ResponseMetadata,
+}
+
+/// Whether an entry of a `ListVersionsResult` is a `Version` or a `DeleteMarker`.
+#[derive(Debug, Clone, Copy, PartialEq, RustcDecodable, RustcEncodable)]
+pub enum VersionEntryKind {
+    Version,
+    DeleteMarker,
 }
 
 //OLD Way - begin
@@ -1442,6 +1580,7 @@ pub struct HeadObjectOutput {
     /// requested, the response will include this header to provide round trip message
     /// integrity verification of the customer-provided encryption key.
     pub sse_customer_key_md5: SSECustomerKeyMD5,
+    pub response_metadata: ResponseMetadata,
 }
 
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
@@ -1450,7 +1589,10 @@ pub struct CopyObjectOutput {
     /// requested, the response will include this header confirming the encryption
     /// algorithm used.
     pub sse_customer_algorithm: SSECustomerAlgorithm,
+    /// Version ID of the source object that was copied.
     pub copy_source_version_id: CopySourceVersionId,
+    /// Version ID of the newly created copy.
+    pub version_id: ObjectVersionId,
     /// The Server-side encryption algorithm used when storing this object in S3
     /// (e.g., AES256, aws:kms).
     pub server_side_encryption: ServerSideEncryption,
@@ -1465,6 +1607,7 @@ pub struct CopyObjectOutput {
     /// If present, specifies the ID of the AWS Key Management Service (KMS) master
     /// encryption key that was used for the object.
     pub ssekms_key_id: SSEKMSKeyId,
+    pub response_metadata: ResponseMetadata,
 }
 
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
@@ -1482,6 +1625,9 @@ pub struct CopyObjectRequest {
     /// the source object. The encryption key provided in this header must be one that
     /// was used when the source object was created.
     pub copy_source_sse_customer_key: Option<CopySourceSSECustomerKey>,
+    /// Customer provided key (SSE-C) of the source object. Takes precedence over the
+    /// `copy_source_sse_customer_*` values which must already be encoded.
+    pub copy_source_sse_customer: Option<CustomerKey>,
     /// The type of storage to use for the object. Defaults to 'STANDARD'.
     pub storage_class: Option<StorageClass>,
     /// Allows grantee to read the object ACL.
@@ -1506,13 +1652,18 @@ pub struct CopyObjectRequest {
     /// appropriate for use with the algorithm specified in the x-amz-server-side-
     /// encryption-customer-algorithm header.
     pub sse_customer_key: Option<SSECustomerKey>,
+    /// Customer provided key (SSE-C). Takes precedence over `sse_customer_algorithm`,
+    /// `sse_customer_key` and `sse_customer_key_md5` which must already be encoded.
+    pub sse_customer: Option<CustomerKey>,
     /// If the bucket is configured as a website, redirects requests for this object
     /// to another object in the same bucket or to an external URL. Amazon S3 stores
     /// the value of this header in the object metadata.
     pub website_redirect_location: Option<WebsiteRedirectLocation>,
     /// The name of the source bucket and key name of the source object, separated by
-    /// a slash (/). Must be URL-encoded.
+    /// a slash (/). The value is URL-encoded when the request is sent so pass it unencoded.
     pub copy_source: CopySource,
+    /// Version ID of the source object to copy. The latest version is copied if `None`.
+    pub copy_source_version_id: Option<CopySourceVersionId>,
     /// The date and time at which the object is no longer cacheable.
     pub expires: Option<Expires>,
     pub key: ObjectKey,
@@ -1545,6 +1696,11 @@ pub struct CopyObjectRequest {
     /// Specifies whether the metadata is copied from the source object or replaced
     /// with metadata provided in the request.
     pub metadata_directive: Option<MetadataDirective>,
+    /// Tags set on the object when `tagging_directive` is REPLACE.
+    pub tagging: Option<TagSet>,
+    /// Specifies whether the tags are copied from the source object or replaced with
+    /// `tagging`. S3 defaults to COPY.
+    pub tagging_directive: Option<TaggingDirective>,
     /// Copies the object if its entity tag (ETag) is different than the specified
     /// ETag.
     pub copy_source_if_none_match: Option<CopySourceIfNoneMatch>,
@@ -1589,6 +1745,9 @@ pub struct HeadObjectRequest {
     /// appropriate for use with the algorithm specified in the x-amz-server-side-
     /// encryption-customer-algorithm header.
     pub sse_customer_key: Option<SSECustomerKey>,
+    /// Customer provided key (SSE-C). Takes precedence over `sse_customer_algorithm`,
+    /// `sse_customer_key` and `sse_customer_key_md5` which must already be encoded.
+    pub sse_customer: Option<CustomerKey>,
     /// Return the object only if it has not been modified since the specified time,
     /// otherwise return a 412 (precondition failed).
     pub if_unmodified_since: Option<IfUnmodifiedSince>,
@@ -1636,6 +1795,59 @@ pub fn multipart_upload_finish_xml(parts: &[String]) -> Result<Vec<u8>, S3Error>
     Ok(response.into_bytes())
 }
 
+/// Writes out the `Delete` XML body used by a multi-object delete. S3 accepts at most 1000 keys
+/// per request.
+pub fn delete_objects_xml(delete: &Delete) -> Result<Vec<u8>, S3Error> {
+    if delete.objects.len() < 1 {
+        return Err(S3Error::new("Can't delete objects. NO keys!"));
+    }
+    if delete.objects.len() > 1000 {
+        return Err(S3Error::new("Can't delete more than 1000 objects in a single request"));
+    }
+
+    let mut response = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?><Delete>");
+
+    if let Some(quiet) = delete.quiet {
+        response = response + &format!("<Quiet>{}</Quiet>", quiet);
+    }
+
+    for object in &delete.objects {
+        response = response + &format!("<Object><Key>{}</Key>", escape_str_pcdata(&object.key));
+        if let Some(ref version_id) = object.version_id {
+            response = response + &format!("<VersionId>{}</VersionId>", escape_str_pcdata(version_id));
+        }
+        response = response + "</Object>";
+    }
+
+    response = response + "</Delete>";
+
+    Ok(response.into_bytes())
+}
+
+/// Writes out the `Tagging` XML body used to set the tags of a bucket or object.
+pub fn tagging_xml(tagging: &Tagging) -> Vec<u8> {
+    let mut xml = String::from("<Tagging><TagSet>");
+
+    for tag in &tagging.tag_set {
+        xml = xml + &format!("<Tag><Key>{}</Key><Value>{}</Value></Tag>",
+                             escape_str_pcdata(&tag.key),
+                             escape_str_pcdata(&tag.value));
+    }
+
+    xml = xml + "</TagSet></Tagging>";
+
+    xml.into_bytes()
+}
+
+/// Returns the value of the x-amz-tagging header which is the tags encoded as URL query
+/// parameters (e.g., Key1=Value1&Key2=Value2).
+pub fn tagging_header_value(tag_set: &TagSet) -> String {
+    tag_set.iter()
+        .map(|tag| format!("{}={}", byte_serialize(&tag.key, true), byte_serialize(&tag.value, true)))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
 // Impls below...
 
 impl TagParser {
@@ -1673,13 +1885,33 @@ impl TagSetParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T)
         -> Result<TagSet, XmlParseError> {
         let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "Tag" {
-            obj.push(try!(TagParser::parse_xml("Tag", stack)));
+        while try!(peek_at_name(stack)) == tag_name {
+            obj.push(try!(TagParser::parse_xml(tag_name, stack)));
         }
         Ok(obj)
     }
 }
 
+impl TaggingParser {
+    pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T)
+        -> Result<Tagging, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = Tagging::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "TagSet" {
+                try!(start_element("TagSet", stack));
+                obj.tag_set = try!(TagSetParser::parse_xml("Tag", stack));
+                try!(end_element("TagSet", stack));
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+
 impl TagSetWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &TagSet) {
         let mut index = 1;
@@ -1882,9 +2114,9 @@ impl MultipartUploadListOutputParser {
                                             "UploadIdMarker", stack));
                 continue;
             }
-            if current_name == "CommonPrefix" {
+            if current_name == "CommonPrefixes" {
                 obj.common_prefixes = try!(CommonPrefixListParser::parse_xml(
-                                            "CommonPrefix", stack));
+                                            "CommonPrefixes", stack));
                 continue;
             }
             if current_name == "NextKeyMarker" {
@@ -1948,7 +2180,7 @@ impl MultipartUploadListOutputWriter {
         UploadIdMarkerWriter::write_params(
                 params, &(prefix.to_string() + "UploadIdMarker"), &obj.upload_id_marker);
         CommonPrefixListWriter::write_params(
-                params, &(prefix.to_string() + "CommonPrefix"), &obj.common_prefixes);
+                params, &(prefix.to_string() + "CommonPrefixes"), &obj.common_prefixes);
         NextKeyMarkerWriter::write_params(
                 params, &(prefix.to_string() + "NextKeyMarker"), &obj.next_key_marker);
         BucketNameWriter::write_params(
@@ -2399,8 +2631,8 @@ impl ListObjectsOutputParser {
                 obj.contents = try!(ObjectMetadataListParser::parse_xml("Contents", stack));
                 continue;
             }
-            if current_name == "CommonPrefix" {
-                obj.common_prefixes = try!(CommonPrefixListParser::parse_xml("CommonPrefix", stack));
+            if current_name == "CommonPrefixes" {
+                obj.common_prefixes = try!(CommonPrefixListParser::parse_xml("CommonPrefixes", stack));
                 continue;
             }
             // Version 2
@@ -2443,7 +2675,7 @@ impl ListObjectsOutputWriter {
         EncodingTypeWriter::write_params(params, &(prefix.to_string() + "EncodingType"), &obj.encoding_type);
         IsTruncatedWriter::write_params(params, &(prefix.to_string() + "IsTruncated"), &obj.is_truncated);
         ObjectMetadataListWriter::write_params(params, &(prefix.to_string() + "Contents"), &obj.contents);
-        CommonPrefixListWriter::write_params(params, &(prefix.to_string() + "CommonPrefix"), &obj.common_prefixes);
+        CommonPrefixListWriter::write_params(params, &(prefix.to_string() + "CommonPrefixes"), &obj.common_prefixes);
     }
 }
 
@@ -2639,12 +2871,6 @@ impl MultipartUploadListPartsRequestWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &MultipartUploadListPartsRequest) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        if let Some(ref obj) = obj.request_payer {
-            RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
-        }
-        BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
-        MultipartUploadIdWriter::write_params(params, &(prefix.to_string() + "uploadId"), &obj.upload_id);
-        ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
         if let Some(ref obj) = obj.max_parts {
             MaxPartsWriter::write_params(params, &(prefix.to_string() + "max-parts"), obj);
         }
@@ -2818,7 +3044,6 @@ impl MultipartUploadListRequestWriter {
         if let Some(ref obj) = obj.upload_id_marker {
             UploadIdMarkerWriter::write_params(params, &(prefix.to_string() + "upload-id-marker"), obj);
         }
-        BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
         if let Some(ref obj) = obj.delimiter {
             DelimiterWriter::write_params(params, &(prefix.to_string() + "delimiter"), obj);
         }
@@ -3160,8 +3385,8 @@ impl DeletedObjectWriter {
 impl DeletedObjectsParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<DeletedObjects, XmlParseError> {
         let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "DeletedObject" {
-            obj.push(try!(DeletedObjectParser::parse_xml("DeletedObject", stack)));
+        while try!(peek_at_name(stack)) == tag_name {
+            obj.push(try!(DeletedObjectParser::parse_xml(tag_name, stack)));
         }
         Ok(obj)
     }
@@ -3178,6 +3403,27 @@ impl DeletedObjectsWriter {
     }
 }
 
+impl DeleteErrorsParser {
+    pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<DeleteErrors, XmlParseError> {
+        let mut obj = Vec::new();
+        while try!(peek_at_name(stack)) == tag_name {
+            obj.push(try!(S3ClientErrorParser::parse_xml(tag_name, stack)));
+        }
+        Ok(obj)
+    }
+}
+
+impl DeleteErrorsWriter {
+    pub fn write_params(params: &mut Params, name: &str, obj: &DeleteErrors) {
+        let mut index = 1;
+        for element in obj.iter() {
+            let key = &format!("{}.{}", name, index);
+            S3ClientErrorWriter::write_params(params, key, element);
+            index += 1;
+        }
+    }
+}
+
 impl DeleteObjectOutputParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<DeleteObjectOutput, XmlParseError> {
         try!(start_element(tag_name, stack));
@@ -3831,12 +4077,12 @@ impl DeleteObjectsOutputParser {
         let mut obj = DeleteObjectsOutput::default();
         loop {
             let current_name = try!(peek_at_name(stack));
-            if current_name == "DeletedObject" {
-                obj.deleted = try!(DeletedObjectsParser::parse_xml("DeletedObject", stack));
+            if current_name == "Deleted" {
+                obj.deleted.extend(try!(DeletedObjectsParser::parse_xml("Deleted", stack)));
                 continue;
             }
             if current_name == "Error" {
-                obj.errors = try!(ErrorsParser::parse_xml("Error", stack));
+                obj.errors.extend(try!(DeleteErrorsParser::parse_xml("Error", stack)));
                 continue;
             }
             if current_name == "x-amz-request-charged" {
@@ -3854,19 +4100,15 @@ impl DeleteObjectsOutputWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &DeleteObjectsOutput) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        DeletedObjectsWriter::write_params(params, &(prefix.to_string() + "DeletedObject"), &obj.deleted);
-        ErrorsWriter::write_params(params, &(prefix.to_string() + "Error"), &obj.errors);
+        DeletedObjectsWriter::write_params(params, &(prefix.to_string() + "Deleted"), &obj.deleted);
+        DeleteErrorsWriter::write_params(params, &(prefix.to_string() + "Error"), &obj.errors);
         RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
     }
 }
 
 impl GetObjectOutput {
     pub fn get_body(&self) -> &[u8] {
-        if self.is_body {
-            return &self.body;
-        }
-
-        &self.body_buffer
+        &self.body
     }
 }
 
@@ -4334,7 +4576,20 @@ impl ListObjectsRequestWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &ListObjectsRequest) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
+        if obj.version == Some(2) {
+            params.put(&(prefix.to_string() + "list-type"), "2");
+            if let Some(ref obj) = obj.continuation_token {
+                ContinuationTokenWriter::write_params(params, &(prefix.to_string() + "continuation-token"), obj);
+            }
+            if let Some(ref obj) = obj.start_after {
+                StartAfterWriter::write_params(params, &(prefix.to_string() + "start-after"), obj);
+            }
+            if let Some(ref obj) = obj.fetch_owner {
+                params.put(&(prefix.to_string() + "fetch-owner"), &obj.to_string());
+            }
+        } else if let Some(ref obj) = obj.marker {
+            MarkerWriter::write_params(params, &(prefix.to_string() + "marker"), obj);
+        }
         if let Some(ref obj) = obj.prefix {
             PrefixWriter::write_params(params, &(prefix.to_string() + "prefix"), obj);
         }
@@ -4344,9 +4599,6 @@ impl ListObjectsRequestWriter {
         if let Some(ref obj) = obj.delimiter {
             DelimiterWriter::write_params(params, &(prefix.to_string() + "delimiter"), obj);
         }
-        if let Some(ref obj) = obj.marker {
-            MarkerWriter::write_params(params, &(prefix.to_string() + "marker"), obj);
-        }
         if let Some(ref obj) = obj.encoding_type {
             EncodingTypeWriter::write_params(params, &(prefix.to_string() + "encoding-type"), obj);
         }
@@ -4380,11 +4632,15 @@ impl ListVersionsResultParser {
                 continue;
             }
             if current_name == "Version" {
-                obj.versions = try!(ObjectVersionListParser::parse_xml("Version", stack));
+                let versions = try!(ObjectVersionListParser::parse_xml("Version", stack));
+                obj.entry_order.extend(versions.iter().map(|_| VersionEntryKind::Version));
+                obj.versions.extend(versions);
                 continue;
             }
             if current_name == "DeleteMarker" {
-                obj.delete_markers = try!(DeleteMarkersParser::parse_xml("DeleteMarker", stack));
+                let markers = try!(DeleteMarkersParser::parse_xml("DeleteMarker", stack));
+                obj.entry_order.extend(markers.iter().map(|_| VersionEntryKind::DeleteMarker));
+                obj.delete_markers.extend(markers);
                 continue;
             }
             if current_name == "NextKeyMarker" {
@@ -4423,8 +4679,8 @@ impl ListVersionsResultParser {
                 obj.version_id_marker = try!(VersionIdMarkerParser::parse_xml("VersionIdMarker", stack));
                 continue;
             }
-            if current_name == "CommonPrefix" {
-                obj.common_prefixes = try!(CommonPrefixListParser::parse_xml("CommonPrefix", stack));
+            if current_name == "CommonPrefixes" {
+                obj.common_prefixes = try!(CommonPrefixListParser::parse_xml("CommonPrefixes", stack));
                 continue;
             }
             break;
@@ -4451,7 +4707,7 @@ impl ListVersionsResultWriter {
         EncodingTypeWriter::write_params(params, &(prefix.to_string() + "EncodingType"), &obj.encoding_type);
         IsTruncatedWriter::write_params(params, &(prefix.to_string() + "IsTruncated"), &obj.is_truncated);
         VersionIdMarkerWriter::write_params(params, &(prefix.to_string() + "VersionIdMarker"), &obj.version_id_marker);
-        CommonPrefixListWriter::write_params(params, &(prefix.to_string() + "CommonPrefix"), &obj.common_prefixes);
+        CommonPrefixListWriter::write_params(params, &(prefix.to_string() + "CommonPrefixes"), &obj.common_prefixes);
     }
 }
 
@@ -4500,7 +4756,6 @@ impl ListObjectVersionsRequestWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &ListObjectVersionsRequest) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
         if let Some(ref obj) = obj.prefix {
             PrefixWriter::write_params(params, &(prefix.to_string() + "prefix"), obj);
         }
@@ -4696,3 +4951,87 @@ impl PrefixWriter {
         params.put(name, obj);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str;
+    use xml::EventReader;
+    use aws::common::xmlutil::XmlResponse;
+
+    fn assert_send<T: Send>() {}
+
+    #[test]
+    fn streaming_requests_are_send() {
+        assert_send::<PutObjectRequest>();
+        assert_send::<MultipartUploadPartRequest>();
+    }
+
+    #[test]
+    fn tagging_round_trip() {
+        let mut tagging = Tagging::default();
+        tagging.tag_set.push(Tag { key: "project".to_string(), value: "blue & green".to_string() });
+        tagging.tag_set.push(Tag { key: "owner".to_string(), value: "ops".to_string() });
+
+        let xml = tagging_xml(&tagging);
+        let body = str::from_utf8(&xml).unwrap();
+        let mut reader = EventReader::from_str(body);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next();
+
+        let parsed = TaggingParser::parse_xml("Tagging", &mut stack).unwrap();
+        assert_eq!(parsed.tag_set, tagging.tag_set);
+        assert_eq!(tagging_header_value(&tagging.tag_set), "project=blue%20%26%20green&owner=ops");
+    }
+
+    #[test]
+    fn delete_result_with_mixed_entries() {
+        let body = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+                    <DeleteResult xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+                    <Deleted><Key>a.txt</Key></Deleted>\
+                    <Error><Key>b.txt</Key><Code>AccessDenied</Code><Message>Access Denied</Message></Error>\
+                    <Deleted><Key>c.txt</Key><VersionId>v1</VersionId></Deleted>\
+                    <Error><Key>d.txt</Key><Code>InternalError</Code><Message>Try again</Message></Error>\
+                    </DeleteResult>";
+        let mut reader = EventReader::from_str(body);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next();
+
+        let parsed = DeleteObjectsOutputParser::parse_xml("DeleteResult", &mut stack).unwrap();
+        let deleted: Vec<&str> = parsed.deleted.iter().map(|d| d.key.as_ref()).collect();
+        let errors: Vec<&str> = parsed.errors.iter().map(|e| e.key.as_ref()).collect();
+        assert_eq!(deleted, vec!["a.txt", "c.txt"]);
+        assert_eq!(parsed.deleted[1].version_id, "v1");
+        assert_eq!(errors, vec!["b.txt", "d.txt"]);
+        assert_eq!(parsed.errors[0].code, "AccessDenied");
+    }
+
+    #[test]
+    fn list_objects_params_for_each_version() {
+        let mut request = ListObjectsRequest::default();
+        request.prefix = Some("photos/".to_string());
+        request.delimiter = Some("/".to_string());
+        request.marker = Some("photos/a.jpg".to_string());
+        request.continuation_token = Some("token".to_string());
+        request.start_after = Some("photos/b.jpg".to_string());
+
+        let mut params = Params::new();
+        ListObjectsRequestWriter::write_params(&mut params, "", &request);
+        assert_eq!(params.get("prefix").map(|s| s.as_str()), Some("photos/"));
+        assert_eq!(params.get("delimiter").map(|s| s.as_str()), Some("/"));
+        assert_eq!(params.get("marker").map(|s| s.as_str()), Some("photos/a.jpg"));
+        assert!(!params.contains_key("list-type"));
+        assert!(!params.contains_key("continuation-token"));
+        assert!(!params.contains_key("start-after"));
+
+        request.version = Some(2);
+        let mut params = Params::new();
+        ListObjectsRequestWriter::write_params(&mut params, "", &request);
+        assert_eq!(params.get("list-type").map(|s| s.as_str()), Some("2"));
+        assert_eq!(params.get("prefix").map(|s| s.as_str()), Some("photos/"));
+        assert_eq!(params.get("delimiter").map(|s| s.as_str()), Some("/"));
+        assert_eq!(params.get("continuation-token").map(|s| s.as_str()), Some("token"));
+        assert_eq!(params.get("start-after").map(|s| s.as_str()), Some("photos/b.jpg"));
+        assert!(!params.contains_key("marker"));
+    }
+}
@@ -0,0 +1,355 @@
+// Copyright 2017 LambdaStack All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Library Documentation
+//!
+//! Iterators that page through the S3 list operations. Each iterator requests a new page only
+//! when the items of the previous page have been consumed. An error ends the iteration after
+//! it has been returned as `Some(Err(S3Error))`.
+//!
+//! ```ignore
+//! let mut request = ListObjectsRequest::default();
+//! request.bucket = "my-bucket".to_string();
+//! request.version = Some(2);
+//!
+//! for object in client.list_objects_iter(&request) {
+//!     match object {
+//!         Ok(object) => println!("{}", object.key),
+//!         Err(e) => println!("{:#?}", e),
+//!     }
+//! }
+//! ```
+
+use std::collections::VecDeque;
+
+use aws::common::credentials::AwsCredentialsProvider;
+use aws::common::request::DispatchSignedRequest;
+use aws::errors::s3::S3Error;
+use aws::s3::object::*;
+use aws::s3::s3client::S3Client;
+
+/// Iterates over every `ObjectMetadata` (`Contents`) of a bucket. Supports both version 1
+/// (marker) and version 2 (continuation-token) listings depending on `ListObjectsRequest.version`.
+/// Common prefixes are not returned by the iterator; use `list_objects` directly if they are needed.
+pub struct ListObjectsIterator<'a, P: 'a, D: 'a>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    client: &'a S3Client<P, D>,
+    request: ListObjectsRequest,
+    items: VecDeque<ObjectMetadata>,
+    done: bool,
+}
+
+/// A single entry returned by `ListObjectVersionsIterator`.
+#[derive(Debug)]
+pub enum ObjectVersionEntry {
+    Version(ObjectVersion),
+    DeleteMarker(DeleteMarkerEntry),
+}
+
+/// Iterates over every `ObjectVersion` and `DeleteMarkerEntry` of a bucket using the key-marker
+/// and version-id-marker of each page.
+pub struct ListObjectVersionsIterator<'a, P: 'a, D: 'a>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    client: &'a S3Client<P, D>,
+    request: ListObjectVersionsRequest,
+    items: VecDeque<ObjectVersionEntry>,
+    done: bool,
+}
+
+/// Iterates over every in-progress `MultipartUpload` of a bucket using the key-marker and
+/// upload-id-marker of each page.
+pub struct MultipartUploadListIterator<'a, P: 'a, D: 'a>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    client: &'a S3Client<P, D>,
+    request: MultipartUploadListRequest,
+    items: VecDeque<MultipartUpload>,
+    done: bool,
+}
+
+/// Iterates over every uploaded `Part` of a multipart upload using the part-number-marker of
+/// each page.
+pub struct MultipartUploadListPartsIterator<'a, P: 'a, D: 'a>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    client: &'a S3Client<P, D>,
+    request: MultipartUploadListPartsRequest,
+    items: VecDeque<Part>,
+    done: bool,
+}
+
+// Impls below...
+
+impl ObjectVersionEntry {
+    /// The object key of the version or delete marker.
+    pub fn key(&self) -> &str {
+        match *self {
+            ObjectVersionEntry::Version(ref version) => &version.key,
+            ObjectVersionEntry::DeleteMarker(ref marker) => &marker.key,
+        }
+    }
+
+    /// Date and time the version or delete marker was last modified.
+    pub fn last_modified(&self) -> &str {
+        match *self {
+            ObjectVersionEntry::Version(ref version) => &version.last_modified,
+            ObjectVersionEntry::DeleteMarker(ref marker) => &marker.last_modified,
+        }
+    }
+}
+
+impl<'a, P, D> ListObjectsIterator<'a, P, D>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    pub fn new(client: &'a S3Client<P, D>, request: &ListObjectsRequest) -> Self {
+        ListObjectsIterator {
+            client: client,
+            request: request.clone(),
+            items: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn next_page(&mut self) -> Result<(), S3Error> {
+        let output = try!(self.client.list_objects(&self.request));
+
+        self.done = !output.is_truncated;
+        if !self.done {
+            if self.request.version == Some(2) {
+                if output.next_continuation_token.is_empty() {
+                    self.done = true;
+                } else {
+                    self.request.continuation_token = Some(output.next_continuation_token.clone());
+                }
+            } else {
+                // NOTE: NextMarker is only returned when a delimiter is given. Otherwise the
+                // last key of the page is the marker for the next request.
+                let marker = if !output.next_marker.is_empty() {
+                    output.next_marker.clone()
+                } else if let Some(last) = output.contents.last() {
+                    last.key.clone()
+                } else if let Some(last) = output.common_prefixes.last() {
+                    last.prefix.clone()
+                } else {
+                    String::new()
+                };
+
+                if marker.is_empty() {
+                    self.done = true;
+                } else {
+                    self.request.marker = Some(marker);
+                }
+            }
+        }
+
+        self.items.extend(output.contents);
+        Ok(())
+    }
+}
+
+impl<'a, P, D> Iterator for ListObjectsIterator<'a, P, D>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    type Item = Result<ObjectMetadata, S3Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.pop_front() {
+                return Some(Ok(item));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.next_page() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+impl<'a, P, D> ListObjectVersionsIterator<'a, P, D>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    pub fn new(client: &'a S3Client<P, D>, request: &ListObjectVersionsRequest) -> Self {
+        ListObjectVersionsIterator {
+            client: client,
+            request: request.clone(),
+            items: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn next_page(&mut self) -> Result<(), S3Error> {
+        let output = try!(self.client.list_object_versions(&self.request));
+
+        self.done = !output.is_truncated || output.next_key_marker.is_empty();
+        if !self.done {
+            self.request.key_marker = Some(output.next_key_marker.clone());
+            self.request.version_id_marker = if output.next_version_id_marker.is_empty() {
+                None
+            } else {
+                Some(output.next_version_id_marker.clone())
+            };
+        }
+
+        // Versions and delete markers are parsed into separate lists so use the recorded entry
+        // order to merge them back into the order S3 returned them.
+        let mut versions = output.versions.into_iter();
+        let mut delete_markers = output.delete_markers.into_iter();
+        let entries = output.entry_order.into_iter().filter_map(|kind| {
+            match kind {
+                VersionEntryKind::Version => versions.next().map(ObjectVersionEntry::Version),
+                VersionEntryKind::DeleteMarker => {
+                    delete_markers.next().map(ObjectVersionEntry::DeleteMarker)
+                }
+            }
+        });
+
+        self.items.extend(entries);
+        Ok(())
+    }
+}
+
+impl<'a, P, D> Iterator for ListObjectVersionsIterator<'a, P, D>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    type Item = Result<ObjectVersionEntry, S3Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.pop_front() {
+                return Some(Ok(item));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.next_page() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+impl<'a, P, D> MultipartUploadListIterator<'a, P, D>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    pub fn new(client: &'a S3Client<P, D>, request: &MultipartUploadListRequest) -> Self {
+        MultipartUploadListIterator {
+            client: client,
+            request: request.clone(),
+            items: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn next_page(&mut self) -> Result<(), S3Error> {
+        let output = try!(self.client.multipart_upload_list(&self.request));
+
+        self.done = !output.is_truncated || output.next_key_marker.is_empty();
+        if !self.done {
+            self.request.key_marker = Some(output.next_key_marker.clone());
+            self.request.upload_id_marker = if output.next_upload_id_marker.is_empty() {
+                None
+            } else {
+                Some(output.next_upload_id_marker.clone())
+            };
+        }
+
+        self.items.extend(output.uploads);
+        Ok(())
+    }
+}
+
+impl<'a, P, D> Iterator for MultipartUploadListIterator<'a, P, D>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    type Item = Result<MultipartUpload, S3Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.pop_front() {
+                return Some(Ok(item));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.next_page() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+impl<'a, P, D> MultipartUploadListPartsIterator<'a, P, D>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    pub fn new(client: &'a S3Client<P, D>, request: &MultipartUploadListPartsRequest) -> Self {
+        MultipartUploadListPartsIterator {
+            client: client,
+            request: request.clone(),
+            items: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn next_page(&mut self) -> Result<(), S3Error> {
+        let output = try!(self.client.multipart_upload_list_parts(&self.request));
+
+        self.done = !output.is_truncated || output.next_part_number_marker == 0;
+        if !self.done {
+            self.request.part_number_marker = Some(output.next_part_number_marker);
+        }
+
+        self.items.extend(output.parts);
+        Ok(())
+    }
+}
+
+impl<'a, P, D> Iterator for MultipartUploadListPartsIterator<'a, P, D>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
+{
+    type Item = Result<Part, S3Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.pop_front() {
+                return Some(Ok(item));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.next_page() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright 2017 LambdaStack All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Library Documentation
+//!
+//! Browser based uploads using HTML forms (POST Object). A `PostPolicy` holds the conditions the
+//! upload must meet. Signing it returns the base64 policy document and the form fields for either
+//! V4 or V2 signatures. See `S3Client::post_policy_form`.
+//!
+//! ```ignore
+//! let mut policy = PostPolicy::new(3600);
+//! policy.set_bucket("my-bucket");
+//! policy.set_key_starts_with("uploads/");
+//! policy.set_acl("public-read");
+//! policy.set_success_action_status(201);
+//! policy.set_content_length_range(1, 10 * 1024 * 1024);
+//!
+//! let form = client.post_policy_form(&policy).unwrap();
+//! // POST to form.url with every entry in form.fields followed by the `file` field.
+//! ```
+
+use std::collections::BTreeMap;
+
+use rustc_serialize::base64::{STANDARD, ToBase64};
+use rustc_serialize::json::Json;
+use time::{Duration, now_utc};
+
+use aws::common::credentials::AwsCredentials;
+use aws::common::region::Region;
+use aws::common::signature::{signature, signature_v2, signing_key};
+use aws::errors::s3::S3Error;
+use aws::s3::endpoint::Signature;
+
+/// A single condition of a POST policy document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PostPolicyCondition {
+    /// The form field must match the value exactly. `{"acl": "public-read"}`
+    Equals(String, String),
+    /// The form field must begin with the value. `["starts-with", "$key", "uploads/"]`
+    StartsWith(String, String),
+    /// The size of the uploaded file in bytes must be within the range.
+    /// `["content-length-range", 1, 1048576]`
+    ContentLengthRange(u64, u64),
+}
+
+/// The conditions and expiration of a browser based upload.
+#[derive(Debug, Clone, Default)]
+pub struct PostPolicy {
+    /// Required. Name of the bucket receiving the upload.
+    pub bucket: String,
+    /// Number of seconds from the time of signing that the policy is valid.
+    pub expires: u32,
+    /// Conditions other than the bucket. Credential related conditions are added when signing.
+    pub conditions: Vec<PostPolicyCondition>,
+    /// Form fields with known values that must be included in the form, such as `key` and `acl`.
+    pub fields: BTreeMap<String, String>,
+}
+
+/// The signed result of a `PostPolicy`.
+#[derive(Debug, Default)]
+pub struct PostPolicyForm {
+    /// Url the form is POSTed to. Only set by `S3Client::post_policy_form`.
+    pub url: String,
+    /// Base64 encoded policy document. It's also included in `fields`.
+    pub policy: String,
+    /// All of the form fields including the policy and signature. The `file` field must follow
+    /// these in the form.
+    pub fields: BTreeMap<String, String>,
+}
+
+// Impls below...
+
+impl PostPolicyCondition {
+    fn to_json(&self) -> Json {
+        match *self {
+            PostPolicyCondition::Equals(ref field, ref value) => {
+                let mut obj = BTreeMap::new();
+                obj.insert(field.trim_left_matches('$').to_string(), Json::String(value.to_string()));
+                Json::Object(obj)
+            },
+            PostPolicyCondition::StartsWith(ref field, ref value) => {
+                Json::Array(vec![Json::String("starts-with".to_string()),
+                                 Json::String(format!("${}", field.trim_left_matches('$'))),
+                                 Json::String(value.to_string())])
+            },
+            PostPolicyCondition::ContentLengthRange(min, max) => {
+                Json::Array(vec![Json::String("content-length-range".to_string()),
+                                 Json::U64(min),
+                                 Json::U64(max)])
+            },
+        }
+    }
+}
+
+impl PostPolicy {
+    /// Creates a policy that expires `expires` seconds after it is signed.
+    pub fn new(expires: u32) -> PostPolicy {
+        PostPolicy { expires: expires, ..PostPolicy::default() }
+    }
+
+    /// Sets the bucket. An exact match condition is added when signing.
+    pub fn set_bucket(&mut self, bucket: &str) {
+        self.bucket = bucket.to_string();
+    }
+
+    /// Object key of the upload must match `key` exactly.
+    pub fn set_key(&mut self, key: &str) {
+        self.set_field_equals("key", key);
+    }
+
+    /// Object key of the upload must begin with `prefix`. The form `key` field defaults to
+    /// `prefix` followed by `${filename}` which S3 replaces with the name of the uploaded file.
+    pub fn set_key_starts_with(&mut self, prefix: &str) {
+        self.remove_field_conditions("key");
+        self.conditions.push(PostPolicyCondition::StartsWith("key".to_string(), prefix.to_string()));
+        self.fields.insert("key".to_string(), format!("{}${{filename}}", prefix));
+    }
+
+    /// Canned ACL of the uploaded object (e.g. `private` or `public-read`).
+    pub fn set_acl(&mut self, acl: &str) {
+        self.set_field_equals("acl", acl);
+    }
+
+    /// HTTP status code (200, 201 or 204) returned on success when no redirect is set.
+    pub fn set_success_action_status(&mut self, status: u16) {
+        self.set_field_equals("success_action_status", &status.to_string());
+    }
+
+    /// Url the browser is redirected to on success.
+    pub fn set_success_action_redirect(&mut self, redirect: &str) {
+        self.set_field_equals("success_action_redirect", redirect);
+    }
+
+    /// Content-Type of the uploaded object must match `content_type` exactly.
+    pub fn set_content_type(&mut self, content_type: &str) {
+        self.set_field_equals("Content-Type", content_type);
+    }
+
+    /// Size of the uploaded file in bytes must be between `min` and `max` inclusive.
+    pub fn set_content_length_range(&mut self, min: u64, max: u64) {
+        self.conditions.retain(|c| match *c {
+            PostPolicyCondition::ContentLengthRange(_, _) => false,
+            _ => true,
+        });
+        self.conditions.push(PostPolicyCondition::ContentLengthRange(min, max));
+    }
+
+    /// Adds any other condition. Fields referenced by the condition must be added to the form
+    /// by the caller.
+    pub fn add_condition(&mut self, condition: PostPolicyCondition) {
+        self.conditions.push(condition);
+    }
+
+    // Adds an exact match condition and the matching form field.
+    fn set_field_equals(&mut self, field: &str, value: &str) {
+        self.remove_field_conditions(field);
+        self.conditions.push(PostPolicyCondition::Equals(field.to_string(), value.to_string()));
+        self.fields.insert(field.to_string(), value.to_string());
+    }
+
+    // Removes any previous condition on the form field.
+    fn remove_field_conditions(&mut self, field: &str) {
+        self.conditions.retain(|c| match *c {
+            PostPolicyCondition::Equals(ref f, _) | PostPolicyCondition::StartsWith(ref f, _) => f != field,
+            _ => true,
+        });
+    }
+
+    /// Builds and signs the policy document returning the policy and all required form fields.
+    /// V4 adds the `x-amz-algorithm`, `x-amz-credential`, `x-amz-date` and `x-amz-signature`
+    /// fields and V2 adds the `AWSAccessKeyId` and `signature` fields.
+    pub fn sign(&self,
+                creds: &AwsCredentials,
+                region: Region,
+                signature_type: &Signature)
+                -> Result<PostPolicyForm, S3Error> {
+        if self.bucket.is_empty() {
+            return Err(S3Error::new("POST policy requires a bucket"));
+        }
+        if self.expires < 1 {
+            return Err(S3Error::new("POST policy must expire at least 1 second from now"));
+        }
+        if !self.fields.contains_key("key") {
+            return Err(S3Error::new("POST policy requires a key or key prefix"));
+        }
+
+        let date = now_utc();
+        let expiration = (date + Duration::seconds(self.expires as i64))
+            .strftime("%Y-%m-%dT%H:%M:%S.000Z")
+            .unwrap()
+            .to_string();
+
+        let mut fields = self.fields.clone();
+        let mut conditions: Vec<Json> = Vec::new();
+        conditions.push(PostPolicyCondition::Equals("bucket".to_string(), self.bucket.clone()).to_json());
+        for condition in &self.conditions {
+            conditions.push(condition.to_json());
+        }
+
+        if let Some(ref token) = *creds.token() {
+            fields.insert("x-amz-security-token".to_string(), token.to_string());
+            conditions.push(PostPolicyCondition::Equals("x-amz-security-token".to_string(), token.to_string()).to_json());
+        }
+
+        let scope = format!("{}/{}/s3/aws4_request", date.strftime("%Y%m%d").unwrap(), region);
+        let credential = format!("{}/{}", creds.aws_access_key_id(), scope);
+        let amz_date = date.strftime("%Y%m%dT%H%M%SZ").unwrap().to_string();
+
+        if *signature_type == Signature::V4 {
+            let v4_fields = [("x-amz-algorithm", "AWS4-HMAC-SHA256"),
+                             ("x-amz-credential", credential.as_str()),
+                             ("x-amz-date", amz_date.as_str())];
+            for &(field, value) in v4_fields.iter() {
+                fields.insert(field.to_string(), value.to_string());
+                conditions.push(PostPolicyCondition::Equals(field.to_string(), value.to_string()).to_json());
+            }
+        }
+
+        let mut document = BTreeMap::new();
+        document.insert("expiration".to_string(), Json::String(expiration));
+        document.insert("conditions".to_string(), Json::Array(conditions));
+        let policy = Json::Object(document).to_string().as_bytes().to_base64(STANDARD);
+
+        match *signature_type {
+            Signature::V4 => {
+                let key = signing_key(creds.aws_secret_access_key(), date, &region.to_string(), "s3");
+                fields.insert("x-amz-signature".to_string(), signature(&policy, key));
+            },
+            Signature::V2 => {
+                fields.insert("AWSAccessKeyId".to_string(), creds.aws_access_key_id().to_string());
+                fields.insert("signature".to_string(), signature_v2(&policy, creds.aws_secret_access_key()));
+            },
+        }
+        fields.insert("policy".to_string(), policy.clone());
+
+        Ok(PostPolicyForm {
+            url: String::new(),
+            policy: policy,
+            fields: fields,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::Json;
+
+    #[test]
+    fn condition_json() {
+        assert_eq!(PostPolicyCondition::Equals("acl".to_string(), "public-read".to_string()).to_json().to_string(),
+                   r#"{"acl":"public-read"}"#);
+        assert_eq!(PostPolicyCondition::StartsWith("key".to_string(), "uploads/".to_string()).to_json().to_string(),
+                   r#"["starts-with","$key","uploads/"]"#);
+        assert_eq!(PostPolicyCondition::ContentLengthRange(1, 1024).to_json(),
+                   Json::from_str(r#"["content-length-range",1,1024]"#).unwrap());
+    }
+
+    #[test]
+    fn key_starts_with_sets_form_key() {
+        let mut policy = PostPolicy::new(60);
+        policy.set_key_starts_with("uploads/");
+        assert_eq!(policy.fields.get("key"), Some(&"uploads/${filename}".to_string()));
+
+        policy.set_key("uploads/exact.txt");
+        assert_eq!(policy.fields.get("key"), Some(&"uploads/exact.txt".to_string()));
+        assert_eq!(policy.conditions,
+                   vec![PostPolicyCondition::Equals("key".to_string(), "uploads/exact.txt".to_string())]);
+    }
+}
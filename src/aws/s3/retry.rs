@@ -0,0 +1,168 @@
+// Copyright 2017 LambdaStack All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Library Documentation
+//!
+//! Retry policy of the S3Client. Failed attempts are retried with exponential backoff and full
+//! jitter: the delay before retry `n` is a random value between 0 and
+//! `min(max_delay, base_delay * 2^n)`. Every attempt is signed again so the credentials and the
+//! request date are always fresh.
+//!
+//! ```ignore
+//! let mut policy = RetryPolicy::default();
+//! policy.max_attempts = 5;
+//! policy.retryable_codes.push("InvalidObjectState".to_string());
+//! client.set_retry_policy(policy);
+//! ```
+
+use std::time::Duration;
+
+use openssl::rand::rand_bytes;
+use xml::EventReader;
+
+use aws::common::request::HttpResponse;
+use aws::common::xmlutil::*;
+use aws::errors::aws::AWSError;
+use aws::errors::s3::{S3Error, S3ErrorKind};
+
+/// RetryPolicy - When and how often a request is sent again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts including the first one. 1 disables retries.
+    pub max_attempts: u32,
+    /// Delay cap of the first retry. It doubles with each retry.
+    pub base_delay: Duration,
+    /// Upper bound of the delay between two attempts.
+    pub max_delay: Duration,
+    /// HTTP statuses that are retried.
+    pub retryable_statuses: Vec<u16>,
+    /// AWS error codes (the `Code` of the error XML) that are retried whatever the status.
+    pub retryable_codes: Vec<String>,
+    /// Retry when the request could not be sent or the response could not be read
+    /// (connection reset, DNS failure, timeout...).
+    pub retry_transport_errors: bool,
+}
+
+// Impls below...
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(20),
+            retryable_statuses: vec![500, 502, 503, 504],
+            retryable_codes: vec!["InternalError".to_string(),
+                                  "RequestTimeout".to_string(),
+                                  "RequestTimeTooSkewed".to_string(),
+                                  "ServiceUnavailable".to_string(),
+                                  "SlowDown".to_string()],
+            retry_transport_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request only once.
+    pub fn none() -> RetryPolicy {
+        RetryPolicy { max_attempts: 1, ..RetryPolicy::default() }
+    }
+
+    /// Returns true if the response of an attempt should be retried.
+    pub fn should_retry_response(&self, response: &HttpResponse) -> bool {
+        if response.status < 300 {
+            return false;
+        }
+        if self.retryable_statuses.contains(&response.status) {
+            return true;
+        }
+        if self.retryable_codes.is_empty() || response.body_str().trim().is_empty() {
+            return false;
+        }
+
+        let mut reader = EventReader::new(&response.body[..]);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next(); // xml start tag
+
+        match AWSError::parse_xml("Error", &mut stack) {
+            Ok(aws) => self.retryable_codes.contains(&aws.code),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns true if the error of an attempt that got no usable response should be retried.
+    pub fn should_retry_error(&self, err: &S3Error) -> bool {
+        self.retry_transport_errors && (err.kind == S3ErrorKind::Transport || err.kind == S3ErrorKind::Timeout)
+    }
+
+    /// Delay before the retry that follows `attempt` (0 for the first attempt).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let cap = self.delay_cap(attempt);
+        let cap_millis = cap.as_secs() * 1000 + (cap.subsec_nanos() / 1_000_000) as u64;
+        if cap_millis == 0 {
+            return cap;
+        }
+
+        let mut buf = [0u8; 8];
+        match rand_bytes(&mut buf) {
+            Ok(_) => {
+                let random = buf.iter().fold(0u64, |acc, byte| (acc << 8) | *byte as u64);
+                Duration::from_millis(random % (cap_millis + 1))
+            },
+            Err(_) => cap,
+        }
+    }
+
+    // min(max_delay, base_delay * 2^attempt)
+    fn delay_cap(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::max_value());
+        match self.base_delay.checked_mul(factor) {
+            Some(delay) if delay < self.max_delay => delay,
+            _ => self.max_delay,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use aws::common::request::HttpResponse;
+
+    #[test]
+    fn retry_classification_and_delay() {
+        let policy = RetryPolicy::default();
+
+        let mut response = HttpResponse::default();
+        response.status = 503;
+        assert!(policy.should_retry_response(&response));
+
+        response.status = 400;
+        response.body = b"<?xml version=\"1.0\" encoding=\"UTF-8\"?><Error><Code>RequestTimeout</Code>\
+                         <Message>Timed out</Message></Error>".to_vec();
+        assert!(policy.should_retry_response(&response));
+
+        response.status = 404;
+        response.body = b"<?xml version=\"1.0\" encoding=\"UTF-8\"?><Error><Code>NoSuchKey</Code>\
+                         <Message>Not found</Message></Error>".to_vec();
+        assert!(!policy.should_retry_response(&response));
+
+        for attempt in 0..40 {
+            assert!(policy.delay(attempt) <= policy.max_delay);
+        }
+        assert!(policy.delay(0) <= Duration::from_millis(100));
+    }
+}
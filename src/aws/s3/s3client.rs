@@ -20,20 +20,34 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use std::str;
 use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::mem;
+use std::sync::Mutex;
+use std::thread;
 use std::time::Instant; //, SystemTime};
 
 use hyper::client::{Client, ProxyConfig, RedirectPolicy};
+use hyper::client::pool::{Config as PoolConfig, Pool};
 use hyper::net::HttpsConnector;
 use hyper_openssl::OpensslClient;
+use openssl::hash::MessageDigest;
+use openssl::ssl::{SslConnectorBuilder, SslMethod, SSL_VERIFY_NONE, SSL_VERIFY_PEER};
+use openssl::x509::{X509, X509_FILETYPE_PEM};
+use rustc_serialize::hex::ToHex;
+use rustc_serialize::json::Json;
 use url::Url;
 use xml::EventReader;
 use chrono::{self, UTC};
 
+use aws::common::body::BodySink;
 use aws::common::credentials::{AwsCredentials, AwsCredentialsProvider};
 use aws::common::region::Region;
 use aws::common::xmlutil::*;
 use aws::common::params::{Params, ServiceParams};
-use aws::common::signature::SignedRequest;
+use aws::common::signature::{SignedRequest, byte_serialize, content_md5};
 use aws::common::request::{DispatchSignedRequest, HttpResponse};
 use aws::common::common::Operation;
 use aws::errors::s3::*;
@@ -43,12 +57,25 @@ use aws::s3::writeparse::*;
 use aws::s3::bucket::*;
 use aws::s3::object::*;
 use aws::s3::acl::*;
+use aws::s3::grant::*;
+use aws::s3::header::ResponseMetadata;
+use aws::s3::policy::Policy;
 use aws::s3::admin::*;
+use aws::s3::paginator::*;
+use aws::s3::postpolicy::{PostPolicy, PostPolicyForm};
+use aws::s3::sse::add_sse_customer_headers;
+use aws::s3::retry::RetryPolicy;
+use http::client::net::TimeoutConnector;
 
 /// Returns a valid hyper client. If proxies are passed in then a proxy version of the client is returned.
 /// If None is passed then in then the default Client is returned.
 ///
-pub fn http_client(proxy: Option<Url>, endpoint: Url) -> Client {
+/// The timeouts and the connection pool of the client are set from `connection` and its TLS
+/// connections use the CA certificates, client certificate and pins of `tls`. Fails if the files
+/// of `tls` can't be loaded (see `tls_client`).
+///
+pub fn http_client(proxy: Option<Url>, endpoint: Url, connection: &ConnectionOptions, tls: &TlsOptions)
+                   -> Result<Client, S3Error> {
     let mut proxy_url: String = String::new();
     let mut proxy_port: u16 = 0;
     let mut proxy_is_https: bool = false;
@@ -92,7 +119,7 @@ pub fn http_client(proxy: Option<Url>, endpoint: Url) -> Client {
         },
     };
 
-    match is_proxy {
+    let mut client = match is_proxy {
         true => {
             let mut is_proxy = true;
 
@@ -107,31 +134,150 @@ pub fn http_client(proxy: Option<Url>, endpoint: Url) -> Client {
 
             match is_proxy {
                 true => {
+                    let ssl = try!(tls_client(tls));
+                    let connector = TimeoutConnector::new(connection.connect_timeout);
+                    // NB: Requests to an https endpoint are tunneled (CONNECT) through the proxy.
+                    // NB: hyper doesn't expose the idle timeout of the proxy pool.
                     match proxy_is_https {
                         true => {
-                            let ssl = OpensslClient::new().unwrap();
-                            let connector = HttpsConnector::new(ssl.clone());
-                            let proxy_config = ProxyConfig::new("https", proxy_url, proxy_port, connector, ssl);
+                            // The pins are for the endpoint and not for the proxy.
+                            let mut proxy_tls = tls.clone();
+                            proxy_tls.pinned_sha256.clear();
+                            let connector = HttpsConnector::with_connector(try!(tls_client(&proxy_tls)), connector);
+                            let mut proxy_config = ProxyConfig::new("https", proxy_url, proxy_port, connector, ssl);
+                            proxy_config.set_pool_config(pool_config(connection));
+                            Client::with_proxy_config(proxy_config)
+                        },
+                        false => {
+                            let mut proxy_config = ProxyConfig::new("http", proxy_url, proxy_port, connector, ssl);
+                            proxy_config.set_pool_config(pool_config(connection));
                             Client::with_proxy_config(proxy_config)
                         },
-                        false => Client::with_http_proxy(proxy_url, proxy_port),
                     }
                 },
                 _ => {
-                    hyper_ssl_client()
+                    try!(hyper_ssl_client(connection, tls))
                 },
             }
         },
         _ => {
-            hyper_ssl_client()
+            try!(hyper_ssl_client(connection, tls))
+        },
+    };
+
+    client.set_read_timeout(connection.read_timeout);
+    client.set_write_timeout(connection.write_timeout);
+    Ok(client)
+}
+
+fn hyper_ssl_client(connection: &ConnectionOptions, tls: &TlsOptions) -> Result<Client, S3Error> {
+    let ssl = try!(tls_client(tls));
+    let connector = HttpsConnector::with_connector(ssl, TimeoutConnector::new(connection.connect_timeout));
+    match pool_config(connection) {
+        Some(config) => {
+            let mut pool = Pool::with_connector(config, connector);
+            pool.set_idle_timeout(connection.idle_timeout);
+            Ok(Client::with_connector(pool))
         },
+        None => Ok(Client::with_connector(connector)),
+    }
+}
+
+/// Returns the OpenSSL client used for the TLS connections of an endpoint. The CA certificates
+/// of `tls` are trusted in addition to the system ones, the client certificate is presented
+/// when the server asks for one and the certificate of the server must match one of the pins if
+/// there are any. `insecure` turns verification off.
+pub fn tls_client(tls: &TlsOptions) -> Result<OpensslClient, S3Error> {
+    try!(check_tls_paths(tls));
+
+    let mut builder = try!(SslConnectorBuilder::new(SslMethod::tls())
+                               .map_err(|err| S3Error::new(format!("Couldn't create the TLS client: {}", err))));
+
+    if let Some(ref ca_file) = tls.ca_file {
+        try!(builder.set_ca_file(ca_file).map_err(|err| tls_error("CA file", ca_file, err)));
+    }
+    if let Some(ref ca_dir) = tls.ca_dir {
+        let entries = try!(fs::read_dir(ca_dir).map_err(|err| tls_error("CA directory", ca_dir, err)));
+        for entry in entries {
+            let path = try!(entry.map_err(|err| tls_error("CA directory", ca_dir, err))).path();
+            if !path.is_file() {
+                continue;
+            }
+            let mut pem = Vec::new();
+            try!(File::open(&path).and_then(|mut file| file.read_to_end(&mut pem))
+                                  .map_err(|err| tls_error("CA file", &path, err)));
+            let certs = try!(X509::stack_from_pem(&pem).map_err(|err| tls_error("CA file", &path, err)));
+            for cert in certs {
+                // NB: The same certificate can be in several files (c_rehash links for example).
+                if let Err(err) = builder.cert_store_mut().add_cert(cert) {
+                    debug!("Skipping CA certificate of {}: {}", path.display(), err);
+                }
+            }
+        }
+    }
+
+    if let Some(ref client_cert) = tls.client_cert {
+        let client_key = tls.client_key.as_ref().unwrap_or(client_cert);
+        try!(builder.set_certificate_chain_file(client_cert)
+                    .map_err(|err| tls_error("client certificate", client_cert, err)));
+        try!(builder.set_private_key_file(client_key, X509_FILETYPE_PEM)
+                    .map_err(|err| tls_error("client key", client_key, err)));
+        try!(builder.check_private_key().map_err(|err| tls_error("client key", client_key, err)));
+    }
+
+    if tls.insecure {
+        warn!("TLS certificate verification is disabled.");
+        builder.set_verify(SSL_VERIFY_NONE);
+    } else if !tls.pinned_sha256.is_empty() {
+        let pins: Vec<String> = tls.pinned_sha256.iter().map(|pin| normalize_fingerprint(pin)).collect();
+        // Only the certificate of the server (depth 0) is pinned. The chain and the host name
+        // are still verified by OpenSSL.
+        builder.set_verify_callback(SSL_VERIFY_PEER, move |preverify_ok, ctx| {
+            if !preverify_ok || ctx.error_depth() != 0 {
+                return preverify_ok;
+            }
+            match ctx.current_cert().and_then(|cert| cert.fingerprint(MessageDigest::sha256()).ok()) {
+                Some(fingerprint) => pins.contains(&fingerprint.to_hex()),
+                None => false,
+            }
+        });
     }
+
+    Ok(OpensslClient::from(builder.build()))
+}
+
+// A missing file is reported with its path before anything is loaded.
+fn check_tls_paths(tls: &TlsOptions) -> Result<(), S3Error> {
+    let paths = [("CA file", &tls.ca_file),
+                 ("CA directory", &tls.ca_dir),
+                 ("client certificate", &tls.client_cert),
+                 ("client key", &tls.client_key)];
+    for &(what, path) in paths.iter() {
+        if let Some(ref path) = *path {
+            try!(fs::metadata(path).map_err(|err| tls_error(what, path, err)));
+        }
+    }
+    Ok(())
+}
+
+fn tls_error<E: fmt::Display>(what: &str, path: &Path, err: E) -> S3Error {
+    S3Error::new(format!("Couldn't load TLS {} {}: {}", what, path.display(), err))
 }
 
-fn hyper_ssl_client() -> Client {
-    let ssl = OpensslClient::new().unwrap();
-    let connector = HttpsConnector::new(ssl);
-    Client::with_connector(connector)
+// Lower case hex without separators like the fingerprints computed by OpenSSL.
+fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint.chars()
+               .filter(|c| *c != ':' && !c.is_whitespace())
+               .flat_map(|c| c.to_lowercase())
+               .collect()
+}
+
+// Connections are only pooled (kept alive) if idle ones may be kept.
+fn pool_config(connection: &ConnectionOptions) -> Option<PoolConfig> {
+    match connection.max_idle_connections {
+        0 => None,
+        max_idle => Some(PoolConfig { max_idle: max_idle }),
+    }
 }
 
 /// S3Client - Base client all
@@ -146,6 +292,8 @@ pub struct S3Client<P, D>
     dispatcher: D,
     region: Region,
     endpoint: Endpoint,
+    retry_policy: RetryPolicy,
+    bucket_regions: Mutex<HashMap<String, Region>>,
 }
 
 impl<P> S3Client<P, Client>
@@ -157,12 +305,27 @@ impl<P> S3Client<P, Client>
     /// let provider = DefaultCredentialsProvider::new(None).unwrap();
     /// ```
     ///
+    /// Use `try_new` if the endpoint has TLS options (`endpoint.tls`). `new` can't return an
+    /// error so it panics if their files can't be loaded.
+    ///
     pub fn new(credentials_provider: P, endpoint: Endpoint) -> Self {
+        match S3Client::try_new(credentials_provider, endpoint) {
+            Ok(client) => client,
+            Err(err) => panic!("{}", err.message),
+        }
+    }
+
+    /// Same as `new` but returns an error if the TLS files of the endpoint (`endpoint.tls`)
+    /// can't be loaded.
+    pub fn try_new(credentials_provider: P, endpoint: Endpoint) -> Result<Self, S3Error> {
         // Hyper client
-        let mut client = http_client(endpoint.proxy.clone(), endpoint.endpoint.clone().unwrap());
+        let mut client = try!(http_client(endpoint.proxy.clone(),
+                                          endpoint.endpoint.clone().unwrap(),
+                                          &endpoint.connection,
+                                          &endpoint.tls));
 
         client.set_redirect_policy(RedirectPolicy::FollowNone);
-        S3Client::with_request_dispatcher(client, credentials_provider, endpoint)
+        Ok(S3Client::with_request_dispatcher(client, credentials_provider, endpoint))
     }
 }
 
@@ -178,9 +341,22 @@ impl<P, D> S3Client<P, D>
             region: endpoint.region.clone(),
             endpoint: endpoint,
             dispatcher: request_dispatcher,
+            retry_policy: RetryPolicy::default(),
+            bucket_regions: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Returns the RetryPolicy used for every request of the S3Client.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Sets the RetryPolicy used for every request of the S3Client. Use `RetryPolicy::none()`
+    /// to send each request only once.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
     /// Returns the current Endpoint of the S3Client.
     pub fn endpoint(&self) -> &Endpoint {
         &self.endpoint
@@ -193,15 +369,15 @@ impl<P, D> S3Client<P, D>
         let mut create_config: Vec<u8>;
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              "/",
                                              &self.endpoint);
                                              //&&self.endpoint);
 
         // If location is not 'us-east-1' create bucket location config.
-        if needs_create_bucket_config(self.region) {
-            create_config = create_bucket_config_xml(self.region);
+        if needs_create_bucket_config(&self.region) {
+            create_config = create_bucket_config_xml(&self.region);
             request.set_payload(Some(&create_config));
         }
 
@@ -212,26 +388,29 @@ impl<P, D> S3Client<P, D>
             None => {},
             Some(ref canned_acl) => request.add_header("x-amz-acl", &canned_acl_in_aws_format(canned_acl)),
         }
+        add_grant_header(&mut request, "x-amz-grant-read", &input.grant_read);
+        add_grant_header(&mut request, "x-amz-grant-write", &input.grant_write);
+        add_grant_header(&mut request, "x-amz-grant-read-acp", &input.grant_read_acp);
+        add_grant_header(&mut request, "x-amz-grant-write-acp", &input.grant_write_acp);
+        add_grant_header(&mut request, "x-amz-grant-full-control", &input.grant_full_control);
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
 
         match status {
             200 => {
-                match result.headers.get("Location") {
-                    Some(ref value) => Ok(CreateBucketOutput { location: value.to_string() }),
-                    None => Ok(CreateBucketOutput { location: "".to_string() }),
-                }
+                Ok(CreateBucketOutput {
+                    location: try!(S3Client::<P,D>::get_value_for_header("Location".to_string(), &result)),
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
-                let mut reader = EventReader::from_str(&result.body);
+                let mut reader = EventReader::new(&result.body[..]);
                 let mut stack = XmlResponse::new(reader.into_iter().peekable());
                 stack.next(); // xml start tag
 
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error creating bucket", aws))
+                Err(S3Error::with_status("Error creating bucket", status, aws))
             },
         }
     }
@@ -244,7 +423,7 @@ impl<P, D> S3Client<P, D>
     pub fn head_bucket(&self, input: &HeadBucketRequest) -> Result<(), S3Error> {
         let mut request = SignedRequest::new("HEAD",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              "/",
                                              &self.endpoint);
@@ -252,11 +431,9 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        //let mut reader = EventReader::from_str(&result.body);
+        //let mut reader = EventReader::new(&result.body[..]);
         //let mut stack = XmlResponse::new(reader.into_iter().peekable());
         //stack.next(); // xml start tag
         //stack.next();
@@ -266,7 +443,12 @@ impl<P, D> S3Client<P, D>
                 Ok(())
             },
             _ => {
-                Err(S3Error::new("Error bucket does not exists or error in retrieving"))
+                // HEAD responses have no body so the code is set from the status.
+                let mut aws = AWSError::default();
+                if status == 404 {
+                    aws.code = "NoSuchBucket".to_string();
+                }
+                Err(S3Error::with_status("Error bucket does not exists or error in retrieving", status, aws))
             },
         }
     }
@@ -277,39 +459,40 @@ impl<P, D> S3Client<P, D>
         let mut request = SignedRequest::new(
                                         "GET",
                                         "s3",
-                                        self.region,
+                                        self.region.clone(),
                                         "",
                                         "/",
                                         &self.endpoint);
 
         request.set_hostname(self.endpoint.hostname());
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
 
         stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(try!(ListBucketsOutputParser::parse_xml("ListAllMyBucketsResult", &mut stack)))
+                let mut output = try!(ListBucketsOutputParser::parse_xml("ListAllMyBucketsResult", &mut stack));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error listing buckets", aws))
+                Err(S3Error::with_status("Error listing buckets", status, aws))
             },
         }
     }
 
     /// Sets lifecycle configuration for your bucket. If a lifecycle configuration
     /// exists, it replaces it.
-    pub fn put_bucket_lifecycle(&self, input: &PutBucketLifecycleRequest) -> Result<(), S3Error> {
+    pub fn put_bucket_lifecycle(&self, input: &PutBucketLifecycleRequest) -> Result<PutBucketLifecycleOutput, S3Error> {
+        let payload: Vec<u8>;
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?lifecycle"} else {"/"},
                                              &self.endpoint);
@@ -317,45 +500,65 @@ impl<P, D> S3Client<P, D>
         if self.endpoint.signature == Signature::V4 {
             let mut params = Params::new();
             params.put("lifecycle", "");
-            //params.put("Action", "PutBucketLifecycle");
-            //PutBucketLifecycleRequestWriter::write_params(&mut params, "", input);
             request.set_params(params);
         }
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        payload = match input.lifecycle_configuration {
+            Some(ref config) => lifecycle_configuration_xml(config),
+            None => return Err(S3Error::new("A lifecycle configuration is required")),
+        };
+        request.set_content_type("application/xml".to_string());
+
+        match input.content_md5 {
+            Some(ref md5) => request.add_header("Content-MD5", md5),
+            None => request.add_header("Content-MD5", &content_md5(&payload)),
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
-        let mut stack = XmlResponse::new(reader.into_iter().peekable());
-        stack.next(); // xml start tag
-        stack.next();
 
         match status {
             200 => {
-                Ok(())
+                Ok(PutBucketLifecycleOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error putting bucket lifecycle", aws))
+                Err(S3Error::with_status("Error putting bucket lifecycle", status, aws))
             },
         }
     }
 
     /// Sets the bucket ACLs
-    pub fn put_bucket_acl(&self, input: &PutBucketAclRequest) -> Result<(), S3Error> {
+    pub fn put_bucket_acl(&self, input: &PutBucketAclRequest) -> Result<PutBucketAclOutput, S3Error> {
+        let policy: Vec<u8>;
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?acl"} else {"/"},
                                              &self.endpoint);
 
-        // Not doing anything but allow unused_variables is set above to kill warning.
-        let acls = build_bucket_acls(&mut request, &input);
+        try!(build_bucket_acls(&mut request, &input));
+
+        if let Some(ref access_control_policy) = input.access_control_policy {
+            policy = access_control_policy_xml(access_control_policy);
+            match input.content_md5 {
+                Some(ref md5) => request.add_header("Content-MD5", md5),
+                None => request.add_header("Content-MD5", &content_md5(&policy)),
+            }
+            request.set_content_type("application/xml".to_string());
+            request.set_payload(Some(&policy));
+        }
 
         if self.endpoint.signature == Signature::V4 {
             let mut params = Params::new();
@@ -366,31 +569,32 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(())
+                Ok(PutBucketAclOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error putting bucket acl", aws))
+                Err(S3Error::with_status("Error putting bucket acl", status, aws))
             },
         }
     }
 
     /// Replaces a policy on a bucket. If the bucket already has a policy, the one in
     /// this request completely replaces it.
-    pub fn put_bucket_policy(&self, input: &PutBucketPolicyRequest) -> Result<(), S3Error> {
+    pub fn put_bucket_policy(&self, input: &PutBucketPolicyRequest) -> Result<PutBucketPolicyOutput, S3Error> {
+        let payload: Vec<u8>;
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?policy"} else {"/"},
                                              &self.endpoint);
@@ -398,38 +602,50 @@ impl<P, D> S3Client<P, D>
         if self.endpoint.signature == Signature::V4 {
             let mut params = Params::new();
             params.put("policy", "");
-            // params.put("Action", "PutBucketPolicy");
-            // PutBucketPolicyRequestWriter::write_params(&mut params, "", input);
             request.set_params(params);
         }
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        if let Err(e) = Json::from_str(&input.policy) {
+            return Err(S3Error::new(&format!("Bucket policy is not valid JSON: {}", e)));
+        }
+        payload = input.policy.clone().into_bytes();
+        request.set_content_type("application/json".to_string());
+
+        match input.content_md5 {
+            Some(ref md5) => request.add_header("Content-MD5", md5),
+            None => request.add_header("Content-MD5", &content_md5(&payload)),
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
-        let mut stack = XmlResponse::new(reader.into_iter().peekable());
-        stack.next(); // xml start tag
 
         match status {
-            200 => {
-                Ok(())
+            200 | 204 => {
+                Ok(PutBucketPolicyOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error putting bucket policy", aws))
+                Err(S3Error::with_status("Error putting bucket policy", status, aws))
             },
         }
     }
 
     /// Set the website configuration for a bucket.
-    pub fn put_bucket_website(&self, input: &PutBucketWebsiteRequest) -> Result<(), S3Error> {
+    pub fn put_bucket_website(&self, input: &PutBucketWebsiteRequest) -> Result<PutBucketWebsiteOutput, S3Error> {
+        let payload: Vec<u8>;
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?website"} else {"/"},
                                              &self.endpoint);
@@ -437,29 +653,37 @@ impl<P, D> S3Client<P, D>
         if self.endpoint.signature == Signature::V4 {
             let mut params = Params::new();
             params.put("website", "");
-            // params.put("Action", "PutBucketWebsite");
-            // PutBucketWebsiteRequestWriter::write_params(&mut params, "", input);
             request.set_params(params);
         }
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        payload = website_configuration_xml(&input.website_configuration);
+        request.set_content_type("application/xml".to_string());
+
+        match input.content_md5 {
+            Some(ref md5) => request.add_header("Content-MD5", md5),
+            None => request.add_header("Content-MD5", &content_md5(&payload)),
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
-        let mut stack = XmlResponse::new(reader.into_iter().peekable());
-        stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(())
+                Ok(PutBucketWebsiteOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error putting bucket website", aws))
+                Err(S3Error::with_status("Error putting bucket website", status, aws))
             },
         }
     }
@@ -467,10 +691,11 @@ impl<P, D> S3Client<P, D>
     /// Set the logging parameters for a bucket and to specify permissions for who can
     /// view and modify the logging parameters. To set the logging status of a bucket,
     /// you must be the bucket owner.
-    pub fn put_bucket_logging(&self, input: &PutBucketLoggingRequest) -> Result<(), S3Error> {
+    pub fn put_bucket_logging(&self, input: &PutBucketLoggingRequest) -> Result<PutBucketLoggingOutput, S3Error> {
+        let payload: Vec<u8>;
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?logging"} else {"/"},
                                              &self.endpoint);
@@ -478,82 +703,194 @@ impl<P, D> S3Client<P, D>
         if self.endpoint.signature == Signature::V4 {
             let mut params = Params::new();
             params.put("logging", "");
-            // params.put("Action", "PutBucketLogging");
-            // PutBucketLoggingRequestWriter::write_params(&mut params, "", input);
             request.set_params(params);
         }
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        payload = bucket_logging_status_xml(&input.bucket_logging_status);
+        request.set_content_type("application/xml".to_string());
+
+        match input.content_md5 {
+            Some(ref md5) => request.add_header("Content-MD5", md5),
+            None => request.add_header("Content-MD5", &content_md5(&payload)),
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
-        let mut stack = XmlResponse::new(reader.into_iter().peekable());
-        stack.next(); // xml start tag
-        stack.next();
 
         match status {
             200 => {
-                Ok(())
+                Ok(PutBucketLoggingOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error putting bucket logging", aws))
+                Err(S3Error::with_status("Error putting bucket logging", status, aws))
             },
         }
     }
 
     /// Creates a new replication configuration (or replaces an existing one, if
     /// present).
-    pub fn put_bucket_replication(&self, input: &PutBucketReplicationRequest) -> Result<(), S3Error> {
+    pub fn put_bucket_replication(&self, input: &PutBucketReplicationRequest) -> Result<PutBucketReplicationOutput, S3Error> {
+        let payload: Vec<u8>;
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
-                                             if self.endpoint.signature == Signature::V2 {"/?replication"} else {""},
+                                             if self.endpoint.signature == Signature::V2 {"/?replication"} else {"/"},
                                              &self.endpoint);
 
         if self.endpoint.signature == Signature::V4 {
             let mut params = Params::new();
             params.put("replication", "");
-            // params.put("Action", "PutBucketReplication");
-            // PutBucketReplicationRequestWriter::write_params(&mut params, "", input);
             request.set_params(params);
         }
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        payload = replication_configuration_xml(&input.replication_configuration);
+        request.set_content_type("application/xml".to_string());
+
+        match input.content_md5 {
+            Some(ref md5) => request.add_header("Content-MD5", md5),
+            None => request.add_header("Content-MD5", &content_md5(&payload)),
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
-        let mut stack = XmlResponse::new(reader.into_iter().peekable());
-        stack.next(); // xml start tag
-        stack.next();
 
         match status {
             200 => {
-                Ok(())
+                Ok(PutBucketReplicationOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
+            },
+            _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error putting bucket replication", status, aws))
+            },
+        }
+    }
+
+    /// Sets the cors configuration for a bucket. If the configuration exists, it replaces it.
+    pub fn put_bucket_cors(&self, input: &PutBucketCorsRequest) -> Result<PutBucketCorsOutput, S3Error> {
+        let payload: Vec<u8>;
+        let mut request = SignedRequest::new("PUT",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             if self.endpoint.signature == Signature::V2 {"/?cors"} else {"/"},
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            let mut params = Params::new();
+            params.put("cors", "");
+            request.set_params(params);
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        payload = cors_configuration_xml(&input.cors_configuration);
+        request.set_content_type("application/xml".to_string());
+
+        match input.content_md5 {
+            Some(ref md5) => request.add_header("Content-MD5", md5),
+            None => request.add_header("Content-MD5", &content_md5(&payload)),
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+
+        match status {
+            200 => {
+                Ok(PutBucketCorsOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
+            },
+            _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error putting bucket cors", status, aws))
+            },
+        }
+    }
+
+    /// Enables notifications of specified events for a bucket. Replaces the topic, queue and
+    /// lambda configurations already set. An empty configuration turns off notifications.
+    pub fn put_bucket_notification_configuration(&self, input: &PutBucketNotificationConfigurationRequest) -> Result<PutBucketNotificationConfigurationOutput, S3Error> {
+        let payload: Vec<u8>;
+        let mut request = SignedRequest::new("PUT",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             if self.endpoint.signature == Signature::V2 {"/?notification"} else {"/"},
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            let mut params = Params::new();
+            params.put("notification", "");
+            request.set_params(params);
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        payload = notification_configuration_xml(&input.notification_configuration);
+        request.set_content_type("application/xml".to_string());
+
+        match input.content_md5 {
+            Some(ref md5) => request.add_header("Content-MD5", md5),
+            None => request.add_header("Content-MD5", &content_md5(&payload)),
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+
+        match status {
+            200 => {
+                Ok(PutBucketNotificationConfigurationOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error putting bucket replication", aws))
+                Err(S3Error::with_status("Error putting bucket notification configuration", status, aws))
             },
         }
     }
 
     /// Sets the versioning state of an existing bucket. To set the versioning state,
     /// you must be the bucket owner.
-    pub fn put_bucket_versioning(&self, input: &PutBucketVersioningRequest) -> Result<(), S3Error> {
+    pub fn put_bucket_versioning(&self, input: &PutBucketVersioningRequest) -> Result<PutBucketVersioningOutput, S3Error> {
         let mut payload: Vec<u8>;
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?versioning"} else {"/"},
                                              &self.endpoint);
@@ -577,22 +914,22 @@ impl<P, D> S3Client<P, D>
         payload = xml.into_bytes();
         request.set_payload(Some(&payload));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
         stack.next();
 
         match status {
             200 => {
-                Ok(())
+                Ok(PutBucketVersioningOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error putting bucket versioning", aws))
+                Err(S3Error::with_status("Error putting bucket versioning", status, aws))
             },
         }
     }
@@ -600,10 +937,10 @@ impl<P, D> S3Client<P, D>
     /// Deletes the bucket. All objects (including all object versions and Delete
     /// Markers) in the bucket must be deleted before the bucket itself can be
     /// deleted.
-    pub fn delete_bucket(&self, input: &DeleteBucketRequest) -> Result<(), S3Error> {
+    pub fn delete_bucket(&self, input: &DeleteBucketRequest) -> Result<DeleteBucketOutput, S3Error> {
         let mut request = SignedRequest::new("DELETE",
                                              "s3",
-                                             self.endpoint.region,
+                                             self.endpoint.region.clone(),
                                              &input.bucket,
                                              "/",
                                              &self.endpoint);
@@ -611,33 +948,33 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let mut result = sign_and_execute(&self.dispatcher,
-                                          &mut request,
-                                          try!(self.credentials_provider.credentials()));
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
 
         match status {
             204 => {
-                Ok(())
+                Ok(DeleteBucketOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
-                let mut reader = EventReader::from_str(&result.body);
+                let mut reader = EventReader::new(&result.body[..]);
                 let mut stack = XmlResponse::new(reader.into_iter().peekable());
                 stack.next(); // xml start tag
 
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error deleting bucket", aws))
+                Err(S3Error::with_status("Error deleting bucket", status, aws))
             },
         }
     }
 
     /// Deletes the tags from the bucket.
-    pub fn delete_bucket_tagging(&self, input: &DeleteBucketTaggingRequest) -> Result<(), S3Error> {
+    pub fn delete_bucket_tagging(&self, input: &DeleteBucketTaggingRequest) -> Result<DeleteBucketTaggingOutput, S3Error> {
         let mut request = SignedRequest::new("DELETE",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
-                                             if self.endpoint.signature == Signature::V2 {"/?tagging"} else {""},
+                                             if self.endpoint.signature == Signature::V2 {"/?tagging"} else {"/"},
                                              &self.endpoint);
 
         if self.endpoint.signature == Signature::V4 {
@@ -651,31 +988,118 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+
+        match status {
+            200 | 204 => {
+                Ok(DeleteBucketTaggingOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
+            },
+            _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error deleting bucket tagging", status, aws))
+            },
+        }
+    }
+
+    /// Sets the tags of a bucket replacing any existing tags.
+    pub fn put_bucket_tagging(&self, input: &PutBucketTaggingRequest) -> Result<PutBucketTaggingOutput, S3Error> {
+        let payload: Vec<u8>;
+        let mut request = SignedRequest::new("PUT",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             if self.endpoint.signature == Signature::V2 {"/?tagging"} else {"/"},
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            let mut params = Params::new();
+            params.put("tagging", "");
+            request.set_params(params);
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        payload = tagging_xml(&input.tagging);
+        request.set_content_type("application/xml".to_string());
+
+        match input.content_md5 {
+            Some(ref md5) => request.add_header("Content-MD5", md5),
+            None => request.add_header("Content-MD5", &content_md5(&payload)),
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+
+        match status {
+            200 | 204 => {
+                Ok(PutBucketTaggingOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
+            },
+            _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error putting bucket tagging", status, aws))
+            },
+        }
+    }
+
+    /// Returns the tags of a bucket.
+    pub fn get_bucket_tagging(&self, input: &GetBucketTaggingRequest) -> Result<GetBucketTaggingOutput, S3Error> {
+        let mut request = SignedRequest::new("GET",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             if self.endpoint.signature == Signature::V2 {"/?tagging"} else {"/"},
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            let mut params = Params::new();
+            params.put("tagging", "");
+            request.set_params(params);
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
-        stack.next();
 
         match status {
             200 => {
-                Ok(())
+                let config = try!(TaggingParser::parse_xml("Tagging", &mut stack));
+                Ok(GetBucketTaggingOutput {
+                    tagging: config,
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error deleting bucket tagging", aws))
+                Err(S3Error::with_status("Error getting bucket tagging", status, aws))
             },
         }
     }
 
     /// Deletes the cors configuration information set for the bucket.
-    pub fn delete_bucket_cors(&self, input: &DeleteBucketCorsRequest) -> Result<(), S3Error> {
+    pub fn delete_bucket_cors(&self, input: &DeleteBucketCorsRequest) -> Result<DeleteBucketCorsOutput, S3Error> {
         let mut request = SignedRequest::new("DELETE",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?cors"} else {"/"},
                                              &self.endpoint);
@@ -691,31 +1115,31 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
         stack.next();
 
         match status {
             200 => {
-                Ok(())
+                Ok(DeleteBucketCorsOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error deleting bucket cors", aws))
+                Err(S3Error::with_status("Error deleting bucket cors", status, aws))
             },
         }
     }
 
     /// This operation removes the website configuration from the bucket.
-    pub fn delete_bucket_website(&self, input: &DeleteBucketWebsiteRequest) -> Result<(), S3Error> {
+    pub fn delete_bucket_website(&self, input: &DeleteBucketWebsiteRequest) -> Result<DeleteBucketWebsiteOutput, S3Error> {
         let mut request = SignedRequest::new("DELETE",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?website"} else {"/"},
                                              &self.endpoint);
@@ -731,31 +1155,31 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
         stack.next();
 
         match status {
             200 => {
-                Ok(())
+                Ok(DeleteBucketWebsiteOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error deleting bucket website", aws))
+                Err(S3Error::with_status("Error deleting bucket website", status, aws))
             },
         }
     }
 
     /// Deletes the policy from the bucket.
-    pub fn delete_bucket_policy(&self, input: &DeleteBucketPolicyRequest) -> Result<(), S3Error> {
+    pub fn delete_bucket_policy(&self, input: &DeleteBucketPolicyRequest) -> Result<DeleteBucketPolicyOutput, S3Error> {
         let mut request = SignedRequest::new("DELETE",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?policy"} else {"/"},
                                              &self.endpoint);
@@ -771,31 +1195,31 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
         stack.next();
 
         match status {
             200 => {
-                Ok(())
+                Ok(DeleteBucketPolicyOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error deleting bucket policy", aws))
+                Err(S3Error::with_status("Error deleting bucket policy", status, aws))
             },
         }
     }
 
     /// Deletes bucket replication.
-    pub fn delete_bucket_replication(&self, input: &DeleteBucketReplicationRequest) -> Result<(), S3Error> {
+    pub fn delete_bucket_replication(&self, input: &DeleteBucketReplicationRequest) -> Result<DeleteBucketReplicationOutput, S3Error> {
         let mut request = SignedRequest::new("DELETE",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?replication"} else {"/"},
                                              &self.endpoint);
@@ -811,22 +1235,22 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
         stack.next();
 
         match status {
             200 => {
-                Ok(())
+                Ok(DeleteBucketReplicationOutput {
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error deleting bucket replication", aws))
+                Err(S3Error::with_status("Error deleting bucket replication", status, aws))
             },
         }
     }
@@ -835,7 +1259,7 @@ impl<P, D> S3Client<P, D>
     pub fn get_bucket_cors(&self, input: &GetBucketCorsRequest) -> Result<GetBucketCorsOutput, S3Error> {
         let mut request = SignedRequest::new("GET",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?cors"} else {"/"},
                                              &self.endpoint);
@@ -851,31 +1275,31 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
-        stack.next();
 
         match status {
             200 => {
-                Ok(try!(GetBucketCorsOutputParser::parse_xml("GetBucketCorsOutput", &mut stack)))
+                // AWS returns CORSConfiguration XML so parse it into GetBucketCorsOutput
+                let mut output = try!(GetBucketCorsOutputParser::parse_xml("CORSConfiguration", &mut stack));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error getting bucket cors", aws))
+                Err(S3Error::with_status("Error getting bucket cors", status, aws))
             },
         }
     }
 
     /// Gets the access control policy for the bucket.
-    pub fn get_bucket_acl(&self, input: &GetBucketAclRequest) -> Result<AccessControlPolicy, S3Error> {
+    pub fn get_bucket_acl(&self, input: &GetBucketAclRequest) -> Result<GetBucketAclOutput, S3Error> {
         let mut request = SignedRequest::new("GET",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?acl"} else {"/"},
                                              &self.endpoint);
@@ -894,21 +1318,24 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(try!(AccessControlPolicyParser::parse_xml("AccessControlPolicy", &mut stack)))
+                let policy = try!(AccessControlPolicyParser::parse_xml("AccessControlPolicy", &mut stack));
+                Ok(GetBucketAclOutput {
+                    owner: policy.owner,
+                    grants: policy.acl.grants,
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error getting bucket acl", aws))
+                Err(S3Error::with_status("Error getting bucket acl", status, aws))
             },
         }
     }
@@ -919,7 +1346,7 @@ impl<P, D> S3Client<P, D>
                 -> Result<GetBucketLoggingOutput, S3Error> {
         let mut request = SignedRequest::new("GET",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?logging"} else {"/"},
                                              &self.endpoint);
@@ -938,42 +1365,198 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(try!(GetBucketLoggingOutputParser::parse_xml("BucketLoggingStatus", &mut stack)))
+                let mut output = try!(GetBucketLoggingOutputParser::parse_xml("BucketLoggingStatus", &mut stack));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error getting bucket logging", aws))
+                Err(S3Error::with_status("Error getting bucket logging", status, aws))
             },
         }
     }
 
-    /// Returns the notification configuration of a bucket.
-    pub fn get_bucket_notification_configuration(&self,
-                                                 input: &GetBucketNotificationConfigurationRequest)
-                                                 -> Result<NotificationConfiguration, S3Error> {
-        let mut request = SignedRequest::new(
-                        "GET",
-                        "s3",
-                        self.region,
-                        &input.bucket,
-                        if self.endpoint.signature == Signature::V2 {"/?notification"} else {"/"},
-                        &self.endpoint);
-
-         // NOTE: V4 - For sub-resources add them as params and not part of the path
-         // NOTE: V2 - For sub-resources add then as part of the path and not as params
+    /// Returns the lifecycle configuration of a bucket.
+    pub fn get_bucket_lifecycle(&self, input: &GetBucketLifecycleRequest) -> Result<GetBucketLifecycleOutput, S3Error> {
+        let mut request = SignedRequest::new("GET",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             if self.endpoint.signature == Signature::V2 {"/?lifecycle"} else {"/"},
+                                             &self.endpoint);
 
-         if self.endpoint.signature == Signature::V4 {
-             let mut params = Params::new();
+        if self.endpoint.signature == Signature::V4 {
+            let mut params = Params::new();
+            params.put("lifecycle", "");
+            request.set_params(params);
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+        let mut reader = EventReader::new(&result.body[..]);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next(); // xml start tag
+
+        match status {
+            200 => {
+                let config = try!(LifecycleConfigurationParser::parse_xml("LifecycleConfiguration", &mut stack));
+                Ok(GetBucketLifecycleOutput {
+                    lifecycle_configuration: config,
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
+            },
+            _ => {
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error getting bucket lifecycle", status, aws))
+            },
+        }
+    }
+
+    /// Returns the website configuration of a bucket.
+    pub fn get_bucket_website(&self, input: &GetBucketWebsiteRequest) -> Result<GetBucketWebsiteOutput, S3Error> {
+        let mut request = SignedRequest::new("GET",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             if self.endpoint.signature == Signature::V2 {"/?website"} else {"/"},
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            let mut params = Params::new();
+            params.put("website", "");
+            request.set_params(params);
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+        let mut reader = EventReader::new(&result.body[..]);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next(); // xml start tag
+
+        match status {
+            200 => {
+                let config = try!(WebsiteConfigurationParser::parse_xml("WebsiteConfiguration", &mut stack));
+                Ok(GetBucketWebsiteOutput {
+                    website_configuration: config,
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
+            },
+            _ => {
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error getting bucket website", status, aws))
+            },
+        }
+    }
+
+    /// Returns the replication configuration of a bucket.
+    pub fn get_bucket_replication(&self, input: &GetBucketReplicationRequest) -> Result<GetBucketReplicationOutput, S3Error> {
+        let mut request = SignedRequest::new("GET",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             if self.endpoint.signature == Signature::V2 {"/?replication"} else {"/"},
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            let mut params = Params::new();
+            params.put("replication", "");
+            request.set_params(params);
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+        let mut reader = EventReader::new(&result.body[..]);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next(); // xml start tag
+
+        match status {
+            200 => {
+                let config = try!(ReplicationConfigurationParser::parse_xml("ReplicationConfiguration", &mut stack));
+                Ok(GetBucketReplicationOutput {
+                    replication_configuration: config,
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
+            },
+            _ => {
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error getting bucket replication", status, aws))
+            },
+        }
+    }
+
+    /// Returns the policy of a bucket as a JSON document.
+    pub fn get_bucket_policy(&self, input: &GetBucketPolicyRequest) -> Result<GetBucketPolicyOutput, S3Error> {
+        let mut request = SignedRequest::new("GET",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             if self.endpoint.signature == Signature::V2 {"/?policy"} else {"/"},
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            let mut params = Params::new();
+            params.put("policy", "");
+            request.set_params(params);
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+        let mut reader = EventReader::new(&result.body[..]);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next(); // xml start tag
+
+        match status {
+            200 => {
+                // NOTE: The policy is returned as JSON and not XML so return the body as is.
+                Ok(GetBucketPolicyOutput {
+                    policy: result.body_str().into_owned(),
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
+            },
+            _ => {
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error getting bucket policy", status, aws))
+            },
+        }
+    }
+
+    /// Returns the notification configuration of a bucket.
+    pub fn get_bucket_notification_configuration(&self,
+                                                 input: &GetBucketNotificationConfigurationRequest)
+                                                 -> Result<GetBucketNotificationConfigurationOutput, S3Error> {
+        let mut request = SignedRequest::new(
+                        "GET",
+                        "s3",
+                        self.region.clone(),
+                        &input.bucket,
+                        if self.endpoint.signature == Signature::V2 {"/?notification"} else {"/"},
+                        &self.endpoint);
+
+         // NOTE: V4 - For sub-resources add them as params and not part of the path
+         // NOTE: V2 - For sub-resources add then as part of the path and not as params
+
+         if self.endpoint.signature == Signature::V4 {
+             let mut params = Params::new();
              params.put("notification", "");
              // params.put("Action", "GetBucketNotificationConfiguration");
              // GetBucketNotificationConfigurationRequestWriter::write_params(&mut params, "", input);
@@ -983,21 +1566,23 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(try!(NotificationConfigurationParser::parse_xml("NotificationConfiguration", &mut stack)))
+                let config = try!(NotificationConfigurationParser::parse_xml("NotificationConfiguration", &mut stack));
+                Ok(GetBucketNotificationConfigurationOutput {
+                    notification_configuration: config,
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error getting bucket notification", aws))
+                Err(S3Error::with_status("Error getting bucket notification", status, aws))
             },
         }
     }
@@ -1008,7 +1593,7 @@ impl<P, D> S3Client<P, D>
                                  -> Result<GetBucketVersioningOutput, S3Error> {
         let mut request = SignedRequest::new("GET",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              if self.endpoint.signature == Signature::V2 {"/?versioning"} else {"/"},
                                              &self.endpoint);
@@ -1027,11 +1612,9 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
 
         stack.next(); // xml start tag
@@ -1039,11 +1622,13 @@ impl<P, D> S3Client<P, D>
             200 => {
                 // AWS Returns VersioningConfiguration XML so parse and create
                 // GetBucketVersioningOutput
-                Ok(try!(GetBucketVersioningOutputParser::parse_xml("VersioningConfiguration", &mut stack)))
+                let mut output = try!(GetBucketVersioningOutputParser::parse_xml("VersioningConfiguration", &mut stack));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error getting bucket versioning", aws))
+                Err(S3Error::with_status("Error getting bucket versioning", status, aws))
             },
         }
     }
@@ -1058,48 +1643,55 @@ impl<P, D> S3Client<P, D>
                         -> Result<ListObjectsOutput, S3Error> {
         let mut request = SignedRequest::new("GET",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              //&format,
                                              "/",
                                              &self.endpoint);
 
-        if input.version != None {
-            let mut params = Params::new();
-            params.put("list-type", "2");
-            // params.put("Action", "ListObjects");
-            // ListObjectsRequestWriter::write_params(&mut params, "", input);
-            request.set_params(params);
-        }
+        // Params are not part of the V2 canonical resource so the same query works for both
+        // signature versions.
+        let mut params = Params::new();
+        ListObjectsRequestWriter::write_params(&mut params, "", input);
+        request.set_params(params);
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
         match status {
             200 => {
-                Ok(try!(ListObjectsOutputParser::parse_xml("ListBucketResult", &mut stack)))
+                let mut output = try!(ListObjectsOutputParser::parse_xml("ListBucketResult", &mut stack));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error listing bucket objects", aws))
+                Err(S3Error::with_status("Error listing bucket objects", status, aws))
             },
         }
     }
 
+    /// Returns an iterator over every object in a bucket. Pages are requested as needed using
+    /// the marker (version 1) or continuation token (version 2) of the previous page.
+    pub fn list_objects_iter<'a>(&'a self, input: &ListObjectsRequest) -> ListObjectsIterator<'a, P, D> {
+        ListObjectsIterator::new(self, input)
+    }
+
     /// The HEAD operation retrieves metadata from an object without returning the
     /// object itself. This operation is useful if you're only interested in an
     /// object's metadata. To use HEAD, you must have READ access to the object.
+    ///
+    /// A failed If-* condition returns an `S3Error` with the `NotModified` (304) or
+    /// `PreconditionFailed` (412) code.
     pub fn head_object(&self, input: &HeadObjectRequest) -> Result<HeadObjectOutput, S3Error> {
         let mut request = SignedRequest::new("HEAD",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              &format!("/{}", input.key),
                                              &self.endpoint);
@@ -1107,25 +1699,49 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        // let mut params = Params::new();
-        // params.put("Action", "HeadObject");
-        // GetObjectRequestWriter::write_params(&mut params, "", input);
-        // request.set_params(params);
+        let mut params = Params::new();
+        if let Some(ref version_id) = input.version_id {
+            params.put("versionId", version_id);
+        }
+        request.set_params(params);
 
-        let mut result = sign_and_execute(&self.dispatcher,
-                                          &mut request,
-                                          try!(self.credentials_provider.credentials()));
+        if let Some(ref range) = input.range {
+            request.add_header("Range", range);
+        }
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer);
+        }
+        add_conditional_headers(&mut request,
+                                &input.if_match,
+                                &input.if_none_match,
+                                &input.if_modified_since,
+                                &input.if_unmodified_since);
+        try!(add_sse_customer_headers(&mut request,
+                                      "x-amz-",
+                                      &input.sse_customer,
+                                      &input.sse_customer_algorithm,
+                                      &input.sse_customer_key,
+                                      &input.sse_customer_key_md5));
+
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
 
         match status {
-            200 => {
+            200...206 => {
                 let head_object = try!(S3Client::<P,D>::head_object_from_response(&mut result));
 
                 Ok(head_object)
             }
+            304 | 412 => {
+                Err(conditional_request_error(&result))
+            }
             _ => {
               let format = format!("Error getting object head with response: {} - {}", status, if status == 404 {"not found"} else {""});
-              Err(S3Error::new(format))
+              let mut aws = AWSError::default();
+              if status == 404 {
+                  aws.code = "NoSuchKey".to_string();
+              }
+              Err(S3Error::with_status(format, status, aws))
             }
         }
     }
@@ -1138,54 +1754,152 @@ impl<P, D> S3Client<P, D>
     /// AWS S3 recommends any GET operations that exceed 300 per second should open
     /// a support ticket to increase the rate. See the link above more details.
     ///
+    /// A failed If-* condition returns an `S3Error` with the `NotModified` (304) or
+    /// `PreconditionFailed` (412) code so cache revalidation can tell them apart from other
+    /// errors.
     pub fn get_object(&self,
                       input: &GetObjectRequest,
                       operation: Option<&mut Operation>)
                       -> Result<GetObjectOutput, S3Error> {
-        let mut request = SignedRequest::new("GET",
-                                             "s3",
-                                             self.region,
-                                             &input.bucket,
-                                             &format!("/{}", input.key),
-                                             &self.endpoint);
+        let mut request = try!(self.get_object_request(input));
+        let mut result = try!(self.new_sign_and_execute(&mut request, operation));
 
-        let hostname = self.hostname(Some(&input.bucket));
-        request.set_hostname(Some(hostname));
+        let status = result.status;
 
-        // let mut params = Params::new();
-        // params.put("Action", "GetObject");
-        // GetObjectRequestWriter::write_params(&mut params, "", input);
-        // request.set_params(params);
-        if let Some(ref range) = input.range {
-            request.add_header("Range", range);
+        match status {
+            200...206 => {
+                let s3_object = try!(S3Client::<P, D>::get_object_from_response(&mut result));
+
+                Ok(s3_object)
+            },
+            304 | 412 => {
+                Err(conditional_request_error(&result))
+            },
+            _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error getting object", status, aws))
+            },
         }
+    }
 
-        let mut result = new_sign_and_execute(&self.dispatcher,
-                                          &mut request,
-                                          operation,
-                                          try!(self.credentials_provider.credentials()));
+    /// Retrieves an object like `get_object` but writes the body to `writer` as it is read so
+    /// large objects (or a `Range` of them) can be downloaded to a file without holding them in
+    /// memory. The `body` of the returned `GetObjectOutput` is empty.
+    ///
+    /// The bytes written are checked against the Content-Length of the response and a truncated
+    /// body is an error. Once part of the body is written the request is not retried so the
+    /// writer never gets the same bytes twice.
+    ///
+    /// ```ignore
+    /// let mut file = try!(File::create("large.iso"));
+    /// let output = try!(client.get_object_to_writer(&request, &mut file, None));
+    /// ```
+    pub fn get_object_to_writer(&self,
+                                input: &GetObjectRequest,
+                                writer: &mut Write,
+                                operation: Option<&mut Operation>)
+                                -> Result<GetObjectOutput, S3Error> {
+        let mut request = try!(self.get_object_request(input));
+        let mut sink = BodySink::new(writer);
+        let mut result = try!(self.execute_operation(&mut request, operation, &mut Some(&mut sink)));
 
         let status = result.status;
 
         match status {
             200...206 => {
                 let s3_object = try!(S3Client::<P, D>::get_object_from_response(&mut result));
+                if s3_object.content_length as u64 != sink.written() {
+                    return Err(S3Error::with_kind(S3ErrorKind::Transport,
+                                                  format!("Object body truncated: got {} of {} bytes",
+                                                          sink.written(),
+                                                          s3_object.content_length)));
+                }
 
                 Ok(s3_object)
             },
+            304 | 412 => {
+                Err(conditional_request_error(&result))
+            },
             _ => {
-                let mut reader = EventReader::from_str(&result.body);
+                let mut reader = EventReader::new(&result.body[..]);
                 let mut stack = XmlResponse::new(reader.into_iter().peekable());
                 stack.next(); // xml start tag
 
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error getting object", aws))
+                Err(S3Error::with_status("Error getting object", status, aws))
             },
         }
     }
 
+    // Builds the GET request of get_object and get_object_to_writer.
+    fn get_object_request<'a>(&'a self, input: &GetObjectRequest) -> Result<SignedRequest<'a>, S3Error> {
+        let mut request = SignedRequest::new("GET",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             &format!("/{}", input.key),
+                                             &self.endpoint);
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        // let mut params = Params::new();
+        // params.put("Action", "GetObject");
+        // GetObjectRequestWriter::write_params(&mut params, "", input);
+        // request.set_params(params);
+
+        // NOTE: The version and response overrides are query params for both V2 and V4.
+        let mut params = Params::new();
+        if let Some(ref version_id) = input.version_id {
+            params.put("versionId", version_id);
+        }
+        if let Some(ref value) = input.response_cache_control {
+            params.put("response-cache-control", value);
+        }
+        if let Some(ref value) = input.response_content_disposition {
+            params.put("response-content-disposition", value);
+        }
+        if let Some(ref value) = input.response_content_encoding {
+            params.put("response-content-encoding", value);
+        }
+        if let Some(ref value) = input.response_content_language {
+            params.put("response-content-language", value);
+        }
+        if let Some(ref value) = input.response_content_type {
+            params.put("response-content-type", value);
+        }
+        if let Some(ref value) = input.response_expires {
+            params.put("response-expires", value);
+        }
+        request.set_params(params);
+
+        if let Some(ref range) = input.range {
+            request.add_header("Range", range);
+        }
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer);
+        }
+        add_conditional_headers(&mut request,
+                                &input.if_match,
+                                &input.if_none_match,
+                                &input.if_modified_since,
+                                &input.if_unmodified_since);
+        try!(add_sse_customer_headers(&mut request,
+                                      "x-amz-",
+                                      &input.sse_customer,
+                                      &input.sse_customer_algorithm,
+                                      &input.sse_customer_key,
+                                      &input.sse_customer_key_md5));
+
+        Ok(request)
+    }
+
     /// Returns the access control list (ACL) of an object.
-    pub fn get_object_acl(&self, input: &GetObjectAclRequest) -> Result<AccessControlPolicy, S3Error> {
+    pub fn get_object_acl(&self, input: &GetObjectAclRequest) -> Result<GetObjectAclOutput, S3Error> {
         let mut path: String;
         if self.endpoint.signature == Signature::V2 {
             path = format!("/{}?acl", input.key);
@@ -1195,7 +1909,7 @@ impl<P, D> S3Client<P, D>
 
         let mut request = SignedRequest::new("GET",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              &path,
                                              &self.endpoint);
@@ -1214,30 +1928,180 @@ impl<P, D> S3Client<P, D>
              request.set_params(params);
          }
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
         match status {
             200 => {
-                Ok(try!(AccessControlPolicyParser::parse_xml("AccessControlPolicy", &mut stack)))
+                let policy = try!(AccessControlPolicyParser::parse_xml("AccessControlPolicy", &mut stack));
+                Ok(GetObjectAclOutput {
+                    owner: policy.owner,
+                    grants: policy.acl.grants,
+                    request_charged: try!(S3Client::<P,D>::get_value_for_header("x-amz-request-charged".to_string(), &result)),
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             }
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error getting object acl", aws))
+                Err(S3Error::with_status("Error getting object acl", status, aws))
             }
         }
     }
 
-    /// Returns a value for a requested header.
+    /// Sets the tags of an object replacing any existing tags.
+    pub fn put_object_tagging(&self, input: &PutObjectTaggingRequest) -> Result<PutObjectTaggingOutput, S3Error> {
+        let payload: Vec<u8>;
+        let path = object_tagging_path(&self.endpoint.signature, &input.key, &input.version_id);
+
+        let mut request = SignedRequest::new("PUT",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             &path,
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            request.set_params(object_tagging_params(&input.version_id));
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        payload = tagging_xml(&input.tagging);
+        request.set_content_type("application/xml".to_string());
+
+        match input.content_md5 {
+            Some(ref md5) => request.add_header("Content-MD5", md5),
+            None => request.add_header("Content-MD5", &content_md5(&payload)),
+        }
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+
+        match status {
+            200 => {
+                let mut output = PutObjectTaggingOutput::default();
+                output.version_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-version-id".to_string(), &result));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
+            },
+            _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error putting object tagging", status, aws))
+            },
+        }
+    }
+
+    /// Returns the tags of an object.
+    pub fn get_object_tagging(&self, input: &GetObjectTaggingRequest) -> Result<GetObjectTaggingOutput, S3Error> {
+        let path = object_tagging_path(&self.endpoint.signature, &input.key, &input.version_id);
+
+        let mut request = SignedRequest::new("GET",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             &path,
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            request.set_params(object_tagging_params(&input.version_id));
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+        let mut reader = EventReader::new(&result.body[..]);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next(); // xml start tag
+
+        match status {
+            200 => {
+                let tagging = try!(TaggingParser::parse_xml("Tagging", &mut stack));
+                Ok(GetObjectTaggingOutput {
+                    version_id: try!(S3Client::<P,D>::get_value_for_header("x-amz-version-id".to_string(), &result)),
+                    tag_set: tagging.tag_set,
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
+            },
+            _ => {
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error getting object tagging", status, aws))
+            },
+        }
+    }
+
+    /// Removes all of the tags of an object.
+    pub fn delete_object_tagging(&self, input: &DeleteObjectTaggingRequest) -> Result<DeleteObjectTaggingOutput, S3Error> {
+        let path = object_tagging_path(&self.endpoint.signature, &input.key, &input.version_id);
+
+        let mut request = SignedRequest::new("DELETE",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             &path,
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            request.set_params(object_tagging_params(&input.version_id));
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+
+        match status {
+            200 | 204 => {
+                let mut output = DeleteObjectTaggingOutput::default();
+                output.version_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-version-id".to_string(), &result));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
+            },
+            _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
+                let aws = try!(AWSError::parse_xml("Error", &mut stack));
+                Err(S3Error::with_status("Error deleting object tagging", status, aws))
+            },
+        }
+    }
+
+    /// Returns a value for a requested header. Header names are compared without case if there
+    /// is no exact match.
     pub fn get_value_for_header(header_name: String, response: &HttpResponse) -> Result<String, S3Error> {
-        match response.headers.get(&header_name) {
-            Some(ref value) => Ok(value.to_string()),
-            _ => Ok(String::new()),
+        if let Some(value) = response.headers.get(&header_name) {
+            return Ok(value.to_string());
         }
+        for (name, value) in response.headers.iter() {
+            if name.eq_ignore_ascii_case(&header_name) {
+                return Ok(value.to_string());
+            }
+        }
+        Ok(String::new())
+    }
+
+    /// Returns the user metadata (x-amz-meta-*) of a response keyed by the name without the
+    /// prefix.
+    pub fn get_metadata_from_headers(response: &HttpResponse) -> Metadata {
+        let mut metadata = Metadata::new();
+        for (name, value) in response.headers.iter() {
+            if name.to_ascii_lowercase().starts_with("x-amz-meta-") {
+                metadata.insert(name["x-amz-meta-".len()..].to_string(), value.to_string());
+            }
+        }
+        metadata
     }
 
     pub fn head_object_from_response(response: &mut HttpResponse) -> Result<HeadObjectOutput, S3Error> {
@@ -1258,13 +2122,13 @@ impl<P, D> S3Client<P, D>
         let server_side_encryption = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption".to_string(), response));
         let ssekms_key_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-aws-kms-key-id".to_string(), response));
         let content_disposition = try!(S3Client::<P,D>::get_value_for_header("Content-Disposition".to_string(), response));
-        let metadata = try!(S3Client::<P,D>::get_value_for_header("x-amz-meta-".to_string(), response));
+        let metadata = S3Client::<P,D>::get_metadata_from_headers(response);
         let website_redirect_location = try!(S3Client::<P,D>::get_value_for_header("x-amz-website-redirect-location".to_string(), response));
         let expires = try!(S3Client::<P,D>::get_value_for_header("Expires".to_string(), response));
         let cache_control = try!(S3Client::<P,D>::get_value_for_header("Cache-Control".to_string(), response));
 
         let content_length_string = try!(S3Client::<P,D>::get_value_for_header("Content-Length".to_string(), response));
-        let content_length = try!(content_length_string.parse::<i32>());
+        let content_length = try!(content_length_string.parse::<i64>());
 
         let expiration = try!(S3Client::<P,D>::get_value_for_header("x-amz-expiration".to_string(), response));
         let missing_meta_string = try!(S3Client::<P,D>::get_value_for_header("x-amz-missing-meta".to_string(), response));
@@ -1293,7 +2157,7 @@ impl<P, D> S3Client<P, D>
             server_side_encryption: server_side_encryption,
             ssekms_key_id: ssekms_key_id,
             content_disposition: content_disposition,
-            metadata: HashMap::new(),
+            metadata: metadata,
             website_redirect_location: website_redirect_location,
             expires: expires,
             cache_control: cache_control,
@@ -1307,6 +2171,7 @@ impl<P, D> S3Client<P, D>
             version_id: version_id,
             e_tag: e_tag,
             sse_customer_key_md5: sse_customer_key_md5,
+            response_metadata: ResponseMetadata::from_response(response),
         };
         Ok(head_object)
     }
@@ -1339,14 +2204,14 @@ impl<P, D> S3Client<P, D>
                                                                         response));
         let content_disposition = try!(S3Client::<P, D>::get_value_for_header("Content-Disposition".to_string(),
                                                                               response));
-        let metadata = try!(S3Client::<P, D>::get_value_for_header("x-amz-meta-".to_string(), response));
+        let metadata = S3Client::<P, D>::get_metadata_from_headers(response);
         let website_redirect_location =
             try!(S3Client::<P, D>::get_value_for_header("x-amz-website-redirect-location".to_string(), response));
         let expires = try!(S3Client::<P, D>::get_value_for_header("Expires".to_string(), response));
         let cache_control = try!(S3Client::<P, D>::get_value_for_header("Cache-Control".to_string(), response));
         let content_length_string = try!(S3Client::<P, D>::get_value_for_header("Content-Length".to_string(),
                                                                                 response));
-        let content_length = try!(content_length_string.parse::<i32>());
+        let content_length = try!(content_length_string.parse::<i64>());
         let expiration = try!(S3Client::<P, D>::get_value_for_header("x-amz-expiration".to_string(), response));
         let missing_meta_string = try!(S3Client::<P, D>::get_value_for_header("x-amz-missing-meta".to_string(),
                                                                               response));
@@ -1380,10 +2245,8 @@ impl<P, D> S3Client<P, D>
             server_side_encryption: server_side_encryption,
             ssekms_key_id: ssekms_key_id,
             content_disposition: content_disposition,
-            metadata: HashMap::new(),
-            is_body: response.is_body,
-            body: response.body.clone().into_bytes(),
-            body_buffer: response.body_buffer.clone(),
+            metadata: metadata,
+            body: mem::replace(&mut response.body, Vec::new()),
             website_redirect_location: website_redirect_location,
             expires: expires,
             cache_control: cache_control,
@@ -1397,71 +2260,206 @@ impl<P, D> S3Client<P, D>
             version_id: version_id,
             e_tag: e_tag,
             sse_customer_key_md5: sse_customer_key_md5,
+            response_metadata: ResponseMetadata::from_response(response),
         };
         Ok(s3_object)
     }
 
     /// Creates a copy of an object that is already stored in Amazon S3.
     pub fn copy_object(&self, input: &CopyObjectRequest) -> Result<CopyObjectOutput, S3Error> {
+        let path: String;
+        if input.key.starts_with("/") {
+          path = input.key.clone();
+        } else {
+          path = format!("/{}", input.key);
+        }
+
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
-                                             &format!("/{}", input.key),
+                                             &path,
                                              &self.endpoint);
-        // let mut params = Params::new();
-        // params.put("Action", "CopyObject");
-        // CopyObjectRequestWriter::write_params(&mut params, "", input);
-        // request.set_params(params);
+
+        request.add_header("x-amz-copy-source", &copy_source(&input.copy_source, &input.copy_source_version_id));
+
+        if let Some(ref directive) = input.metadata_directive {
+            request.add_header("x-amz-metadata-directive", directive);
+        }
+        if let Some(ref directive) = input.tagging_directive {
+            request.add_header("x-amz-tagging-directive", directive);
+        }
+        if let Some(ref tagging) = input.tagging {
+            request.add_header("x-amz-tagging", &tagging_header_value(tagging));
+        }
+        if let Some(ref metadata) = input.metadata {
+            for (key, value) in metadata {
+                request.add_header(&format!("x-amz-meta-{}", key), value);
+            }
+        }
+
+        // Conditional copy
+        if let Some(ref etag) = input.copy_source_if_match {
+            request.add_header("x-amz-copy-source-if-match", etag);
+        }
+        if let Some(ref etag) = input.copy_source_if_none_match {
+            request.add_header("x-amz-copy-source-if-none-match", etag);
+        }
+        if let Some(ref since) = input.copy_source_if_modified_since {
+            request.add_header("x-amz-copy-source-if-modified-since", since);
+        }
+        if let Some(ref since) = input.copy_source_if_unmodified_since {
+            request.add_header("x-amz-copy-source-if-unmodified-since", since);
+        }
+
+        if let Some(ref class) = input.storage_class {
+            request.add_header("x-amz-storage-class", class);
+        }
+        if let Some(ref location) = input.website_redirect_location {
+            request.add_header("x-amz-website-redirect-location", location);
+        }
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer);
+        }
+
+        // ACLs
+        if let Some(ref acl) = input.acl {
+            request.add_header("x-amz-acl", &canned_acl_in_aws_format(acl));
+        }
+        add_grant_header(&mut request, "x-amz-grant-read", &input.grant_read);
+        add_grant_header(&mut request, "x-amz-grant-read-acp", &input.grant_read_acp);
+        add_grant_header(&mut request, "x-amz-grant-write-acp", &input.grant_write_acp);
+        add_grant_header(&mut request, "x-amz-grant-full-control", &input.grant_full_control);
+
+        // Server side encryption of the new object
+        if let Some(ref sse) = input.server_side_encryption {
+            if sse.to_string().to_ascii_lowercase() == "aes256" {
+                request.add_header("x-amz-server-side-encryption", sse);
+            } else {
+                match input.ssekms_key_id {
+                    Some(ref key_id) => request.add_header("x-amz-server-side-encryption-aws-kms-key-id", key_id),
+                    None => return Err(S3Error::new("KMS key specified but no key id provided.")),
+                }
+                request.add_header("x-amz-server-side-encryption", "aws:kms");
+            }
+        }
+        try!(add_sse_customer_headers(&mut request,
+                                      "x-amz-",
+                                      &input.sse_customer,
+                                      &input.sse_customer_algorithm,
+                                      &input.sse_customer_key,
+                                      &input.sse_customer_key_md5));
+
+        // Customer key used to decrypt the source object
+        try!(add_sse_customer_headers(&mut request,
+                                      "x-amz-copy-source-",
+                                      &input.copy_source_sse_customer,
+                                      &input.copy_source_sse_customer_algorithm,
+                                      &input.copy_source_sse_customer_key,
+                                      &input.copy_source_sse_customer_key_md5));
+
+        // Only used when the metadata directive is REPLACE
+        if let Some(ref cache_control) = input.cache_control {
+            request.add_header("Cache-Control", cache_control);
+        }
+        if let Some(ref content_disposition) = input.content_disposition {
+            request.add_header("Content-Disposition", content_disposition);
+        }
+        if let Some(ref content_encoding) = input.content_encoding {
+            request.add_header("Content-Encoding", content_encoding);
+        }
+        if let Some(ref content_language) = input.content_language {
+            request.add_header("Content-Language", content_language);
+        }
+        if let Some(ref expires) = input.expires {
+            request.add_header("Expires", expires);
+        }
+        if let Some(ref content_type) = input.content_type {
+            request.set_content_type(content_type.to_string());
+        }
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next(); // xml start tag
+
+        // NOTE: S3 can return a 200 and still fail the copy. In that case the body is an Error.
+        if status == 200 && try!(peek_at_name(&mut stack)) == "CopyObjectResult" {
+            let mut output = CopyObjectOutput::default();
+            output.copy_object_result = try!(CopyObjectResultParser::parse_xml("CopyObjectResult", &mut stack));
+            output.copy_source_version_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-copy-source-version-id".to_string(), &result));
+            output.version_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-version-id".to_string(), &result));
+            output.expiration = try!(S3Client::<P,D>::get_value_for_header("x-amz-expiration".to_string(), &result));
+            output.server_side_encryption = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption".to_string(), &result));
+            output.ssekms_key_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-aws-kms-key-id".to_string(), &result));
+            output.sse_customer_algorithm = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-customer-algorithm".to_string(), &result));
+            output.sse_customer_key_md5 = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-customer-key-MD5".to_string(), &result));
+            output.request_charged = try!(S3Client::<P,D>::get_value_for_header("x-amz-request-charged".to_string(), &result));
+            output.response_metadata = ResponseMetadata::from_response(&result);
+            Ok(output)
+        } else {
+            let aws = try!(AWSError::parse_xml("Error", &mut stack));
+            Err(S3Error::with_status("Error copying object", status, aws))
+        }
+    }
+
+    /// This operation enables you to delete multiple objects from a bucket using a
+    /// single HTTP request. You may specify up to 1000 keys.
+    pub fn delete_objects(&self, input: &DeleteObjectsRequest) -> Result<DeleteObjectsOutput, S3Error> {
+        let mut payload: Vec<u8>;
+        let mut request = SignedRequest::new("POST",
+                                             "s3",
+                                             self.region.clone(),
+                                             &input.bucket,
+                                             if self.endpoint.signature == Signature::V2 {"/?delete"} else {"/"},
+                                             &self.endpoint);
+
+        if self.endpoint.signature == Signature::V4 {
+            let mut params = Params::new();
+            params.put("delete", "");
+            request.set_params(params);
+        }
+
+        let hostname = self.hostname(Some(&input.bucket));
+        request.set_hostname(Some(hostname));
+
+        if let Some(ref mfa) = input.mfa {
+            request.add_header("x-amz-mfa", mfa);
+        }
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer);
+        }
+
+        payload = try!(delete_objects_xml(&input.delete));
+        // NOTE: Content-MD5 is required by S3 for multi-object deletes.
+        request.add_header("Content-MD5", &content_md5(&payload));
+        request.set_content_type("application/xml".to_string());
+        request.set_payload(Some(&payload));
+
+        let result = try!(self.sign_and_execute(&mut request));
+        let status = result.status;
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
-        stack.next();
 
         match status {
             200 => {
-                Ok(try!(CopyObjectOutputParser::parse_xml("CopyObjectOutput", &mut stack)))
+                let mut output = try!(DeleteObjectsOutputParser::parse_xml("DeleteResult", &mut stack));
+                output.request_charged = try!(S3Client::<P,D>::get_value_for_header("x-amz-request-charged".to_string(), &result));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error copying object", aws))
+                Err(S3Error::with_status("Error deleting objects", status, aws))
             },
         }
     }
 
-    /// This operation enables you to delete multiple objects from a bucket using a
-    /// single HTTP request. You may specify up to 1000 keys.
-    pub fn delete_objects(&self, input: &DeleteObjectsRequest) -> Result<DeleteObjectsOutput, S3Error> {
-        // let mut uri = String::from("/");
-        // uri = uri +  &input.key.to_string();
-        // let mut request = SignedRequest::new("DELETE", "s3", self.region, &uri);
-        // let mut params = Params::new();
-        //
-        // let hostname = self.hostname(Some(&input.bucket));
-        // request.set_hostname(Some(hostname));
-        //
-        // params.put("Action", "DeleteObjects");
-        // DeleteObjectsRequestWriter::write_params(&mut params, "", input);
-        // request.set_params(params);
-        // let result = sign_and_execute(&self.dispatcher, &mut request, try!(self.credentials_provider.credentials()));
-        // let status = result.status;
-        // match status {
-        //  200 => {
-        //      Ok(try!(DeleteObjectsOutputParser::parse_xml("DeleteObjectsOutput", &mut stack)))
-        //  }
-        //  _ => { Err(S3Error::new("error")) }
-        // }
-        Err(S3Error::new("not implemented"))
-    }
-
     /// Deletes a given object from the bucket.
     pub fn delete_object(&self,
                          input: &DeleteObjectRequest,
@@ -1480,7 +2478,7 @@ impl<P, D> S3Client<P, D>
 
         let mut request = SignedRequest::new("DELETE",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              &path,
                                              &self.endpoint);
@@ -1496,25 +2494,28 @@ impl<P, D> S3Client<P, D>
           request.set_params(params);
         }
 
-        let result = new_sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      operation,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.new_sign_and_execute(&mut request, operation));
         let status = result.status;
 
         match status {
             204 => {
                 // NOTE: No payload with 204 but there can be two headers
                 // x-amz-delete-marker: true
-                Ok(DeleteObjectOutput::default())
+                let mut output = DeleteObjectOutput::default();
+                let delete_marker = try!(S3Client::<P,D>::get_value_for_header("x-amz-delete-marker".to_string(), &result));
+                output.delete_marker = delete_marker == "true";
+                output.version_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-version-id".to_string(), &result));
+                output.request_charged = try!(S3Client::<P,D>::get_value_for_header("x-amz-request-charged".to_string(), &result));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
-                let mut reader = EventReader::from_str(&result.body);
+                let mut reader = EventReader::new(&result.body[..]);
                 let mut stack = XmlResponse::new(reader.into_iter().peekable());
                 stack.next(); // xml start tag
 
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error deleting object", aws))
+                Err(S3Error::with_status("Error deleting object", status, aws))
             },
         }
     }
@@ -1544,7 +2545,7 @@ impl<P, D> S3Client<P, D>
 
         let mut request = SignedRequest::new("POST",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              &path,
                                              &self.endpoint);
@@ -1555,25 +2556,48 @@ impl<P, D> S3Client<P, D>
             request.set_params(params);
         }
 
+        if let Some(ref acl) = input.acl {
+            request.add_header("x-amz-acl", acl);
+        }
+        add_grant_header(&mut request, "x-amz-grant-read", &input.grant_read);
+        add_grant_header(&mut request, "x-amz-grant-read-acp", &input.grant_read_acp);
+        add_grant_header(&mut request, "x-amz-grant-write-acp", &input.grant_write_acp);
+        add_grant_header(&mut request, "x-amz-grant-full-control", &input.grant_full_control);
+
+        if let Some(ref tagging) = input.tagging {
+            request.add_header("x-amz-tagging", &tagging_header_value(tagging));
+        }
+        try!(add_sse_customer_headers(&mut request,
+                                      "x-amz-",
+                                      &input.sse_customer,
+                                      &input.sse_customer_algorithm,
+                                      &input.sse_customer_key,
+                                      &input.sse_customer_key_md5));
+
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
 
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(try!(MultipartUploadCreateOutputParser::parse_xml("InitiateMultipartUploadResult", &mut stack)))
+                let mut output = try!(MultipartUploadCreateOutputParser::parse_xml("InitiateMultipartUploadResult", &mut stack));
+                output.sse_customer_algorithm = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-customer-algorithm".to_string(), &result));
+                output.sse_customer_key_md5 = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-customer-key-MD5".to_string(), &result));
+                output.server_side_encryption = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption".to_string(), &result));
+                output.ssekms_key_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-aws-kms-key-id".to_string(), &result));
+                output.request_charged = try!(S3Client::<P,D>::get_value_for_header("x-amz-request-charged".to_string(), &result));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error creating multipart object upload", aws))
+                Err(S3Error::with_status("Error creating multipart object upload", status, aws))
             },
         }
     }
@@ -1597,12 +2621,13 @@ impl<P, D> S3Client<P, D>
 
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              &path,
                                              &self.endpoint);
 
         request.set_payload(input.body);
+        request.set_payload_stream(input.body_stream.as_ref());
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
@@ -1610,6 +2635,12 @@ impl<P, D> S3Client<P, D>
         if let Some(ref md5) = input.content_md5 {
             request.add_header("Content-MD5", md5);
         }
+        try!(add_sse_customer_headers(&mut request,
+                                      "x-amz-",
+                                      &input.sse_customer,
+                                      &input.sse_customer_algorithm,
+                                      &input.sse_customer_key,
+                                      &input.sse_customer_key_md5));
 
         if self.endpoint.signature == Signature::V4 {
             let mut params = Params::new();
@@ -1618,9 +2649,7 @@ impl<P, D> S3Client<P, D>
             request.set_params(params);
         }
 
-        let mut result = sign_and_execute(&self.dispatcher,
-                                          &mut request,
-                                          try!(self.credentials_provider.credentials()));
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
 
         match status {
@@ -1631,12 +2660,12 @@ impl<P, D> S3Client<P, D>
                 }
             },
             _ => {
-                let mut reader = EventReader::from_str(&result.body);
+                let mut reader = EventReader::new(&result.body[..]);
                 let mut stack = XmlResponse::new(reader.into_iter().peekable());
                 stack.next(); // xml start tag
 
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error uploading a part", aws))
+                Err(S3Error::with_status("Error uploading a part", status, aws))
             },
         }
     }
@@ -1657,7 +2686,7 @@ impl<P, D> S3Client<P, D>
 
         let mut request = SignedRequest::new("POST",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              &path,
                                              &self.endpoint);
@@ -1673,22 +2702,27 @@ impl<P, D> S3Client<P, D>
 
         request.set_payload(input.multipart_upload);
 
-        let mut result = sign_and_execute(&self.dispatcher,
-                                          &mut request,
-                                          try!(self.credentials_provider.credentials()));
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
 
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(try!(MultipartUploadCompleteOutputParser::parse_xml("CompleteMultipartUploadResult", &mut stack)))
+                let mut output = try!(MultipartUploadCompleteOutputParser::parse_xml("CompleteMultipartUploadResult", &mut stack));
+                output.version_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-version-id".to_string(), &result));
+                output.expiration = try!(S3Client::<P,D>::get_value_for_header("x-amz-expiration".to_string(), &result));
+                output.server_side_encryption = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption".to_string(), &result));
+                output.ssekms_key_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-aws-kms-key-id".to_string(), &result));
+                output.request_charged = try!(S3Client::<P,D>::get_value_for_header("x-amz-request-charged".to_string(), &result));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error completing multipart upload", aws))
+                Err(S3Error::with_status("Error completing multipart upload", status, aws))
             },
         }
     }
@@ -1699,84 +2733,94 @@ impl<P, D> S3Client<P, D>
         let mut request = SignedRequest::new(
                                         "GET",
                                         "s3",
-                                        self.region,
-                                        &input.bucket,
-                                        if self.endpoint.signature == Signature::V4 {"/"} else {"/?uploads"},
-                                        &self.endpoint);
-
-        if self.endpoint.signature == Signature::V4 {
-            let mut params = Params::new();
-            params.put("uploads", "");
-            request.set_params(params);
-        }
+                                        self.region.clone(),
+                                        &input.bucket,
+                                        "/",
+                                        &self.endpoint);
+
+        // NOTE: The sub-resource is a param for V2 too since the markers are added as params.
+        let mut params = Params::new();
+        params.put("uploads", "");
+        MultipartUploadListRequestWriter::write_params(&mut params, "", input);
+        request.set_params(params);
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher, &mut request, try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(try!(MultipartUploadListOutputParser::parse_xml("ListMultipartUploadsResult", &mut stack)))
+                let mut output = try!(MultipartUploadListOutputParser::parse_xml("ListMultipartUploadsResult", &mut stack));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             }
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error completing list_multipart_uploads", aws))
+                Err(S3Error::with_status("Error completing list_multipart_uploads", status, aws))
             }
         }
     }
 
+    /// Returns an iterator over every in-progress multipart upload in a bucket.
+    pub fn multipart_upload_list_iter<'a>(&'a self, input: &MultipartUploadListRequest) -> MultipartUploadListIterator<'a, P, D> {
+        MultipartUploadListIterator::new(self, input)
+    }
+
     /// Lists the parts that have been uploaded for a specific multipart upload.
     pub fn multipart_upload_list_parts(&self, input: &MultipartUploadListPartsRequest) -> Result<MultipartUploadListPartsOutput, S3Error> {
-        let object_name = &input.key;
-        let upload_id = &input.upload_id;
-        let path: String;
-
-        if self.endpoint.signature == Signature::V2 {
-            path = format!("/{}?uploadId={}", object_name, upload_id);
-        } else {
-            path = format!("/{}", object_name);
-        }
+        let path = format!("/{}", input.key);
 
         let mut request = SignedRequest::new(
                                         "GET",
                                         "s3",
-                                        self.region,
+                                        self.region.clone(),
                                         &input.bucket,
                                         &path,
                                         &self.endpoint);
 
-        if self.endpoint.signature == Signature::V4 {
-            let mut params = Params::new();
-            params.put("uploadId", &input.upload_id.to_string());
-            request.set_params(params);
+        // NOTE: The sub-resource is a param for V2 too since the part-number-marker is added as a param.
+        let mut params = Params::new();
+        params.put("uploadId", &input.upload_id.to_string());
+        MultipartUploadListPartsRequestWriter::write_params(&mut params, "", input);
+        request.set_params(params);
+
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer);
         }
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let mut result = sign_and_execute(&self.dispatcher, &mut request, try!(self.credentials_provider.credentials()));
+        let mut result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
 
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(try!(MultipartUploadListPartsOutputParser::parse_xml("ListPartsResult", &mut stack)))
+                let mut output = try!(MultipartUploadListPartsOutputParser::parse_xml("ListPartsResult", &mut stack));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             }
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error completing list_parts", aws))
+                Err(S3Error::with_status("Error completing list_parts", status, aws))
             }
         }
     }
 
+    /// Returns an iterator over every part uploaded for a specific multipart upload.
+    pub fn multipart_upload_list_parts_iter<'a>(&'a self, input: &MultipartUploadListPartsRequest) -> MultipartUploadListPartsIterator<'a, P, D> {
+        MultipartUploadListPartsIterator::new(self, input)
+    }
+
     /// Aborts a multipart upload.
     /// To verify that all parts have been removed, so you don't get charged for the
     /// part storage, you should call the List Parts operation and ensure the parts
@@ -1796,7 +2840,7 @@ impl<P, D> S3Client<P, D>
         let mut request = SignedRequest::new(
                                         "DELETE",
                                         "s3",
-                                        self.region,
+                                        self.region.clone(),
                                         &input.bucket,
                                         &path,
                                         &self.endpoint);
@@ -1810,19 +2854,22 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher, &mut request, try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
         stack.next(); // xml start tag
 
         match status {
             204 => {
-                Ok(MultipartUploadAbortOutput::default())
+                let mut output = MultipartUploadAbortOutput::default();
+                output.request_charged = try!(S3Client::<P,D>::get_value_for_header("x-amz-request-charged".to_string(), &result));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             }
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error completing list_parts", aws))
+                Err(S3Error::with_status("Error completing list_parts", status, aws))
             }
         }
     }
@@ -1838,7 +2885,7 @@ impl<P, D> S3Client<P, D>
 
         let mut request = SignedRequest::new("POST",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              &path,
                                              &self.endpoint);
@@ -1851,22 +2898,25 @@ impl<P, D> S3Client<P, D>
             request.set_params(params);
         }
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
-        let mut stack = XmlResponse::new(reader.into_iter().peekable());
-        stack.next(); // xml start tag
-        stack.next();
 
         match status {
-            200 => {
-                Ok(try!(RestoreObjectOutputParser::parse_xml("RestoreObjectOutput", &mut stack)))
+            // NOTE: 202 when the restore is started and 200 when the object is already restored.
+            // Neither has a body.
+            200 | 202 => {
+                let mut output = RestoreObjectOutput::default();
+                output.request_charged = try!(S3Client::<P,D>::get_value_for_header("x-amz-request-charged".to_string(), &result));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
+                let mut reader = EventReader::new(&result.body[..]);
+                let mut stack = XmlResponse::new(reader.into_iter().peekable());
+                stack.next(); // xml start tag
+
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error restoring object", aws))
+                Err(S3Error::with_status("Error restoring object", status, aws))
             },
         }
     }
@@ -1891,7 +2941,7 @@ impl<P, D> S3Client<P, D>
 
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              &path,
                                              &self.endpoint);
@@ -1911,6 +2961,12 @@ impl<P, D> S3Client<P, D>
                 request.add_header("x-amz-server-side-encryption", "aws:kms");
             }
         }
+        try!(add_sse_customer_headers(&mut request,
+                                      "x-amz-",
+                                      &input.sse_customer,
+                                      &input.sse_customer_algorithm,
+                                      &input.sse_customer_key,
+                                      &input.sse_customer_key_md5));
 
         if let Some(ref cache_control) = input.cache_control {
             request.add_header("Cache-Control", cache_control);
@@ -1929,6 +2985,14 @@ impl<P, D> S3Client<P, D>
         if let Some(ref acl) = input.acl {
             request.add_header("x-amz-acl", &canned_acl_in_aws_format(acl));
         }
+        add_grant_header(&mut request, "x-amz-grant-read", &input.grant_read);
+        add_grant_header(&mut request, "x-amz-grant-read-acp", &input.grant_read_acp);
+        add_grant_header(&mut request, "x-amz-grant-write-acp", &input.grant_write_acp);
+        add_grant_header(&mut request, "x-amz-grant-full-control", &input.grant_full_control);
+
+        if let Some(ref tagging) = input.tagging {
+            request.add_header("x-amz-tagging", &tagging_header_value(tagging));
+        }
 
         match input.content_type {
             Some(ref content_type) => request.set_content_type(content_type.to_string()),
@@ -1940,33 +3004,41 @@ impl<P, D> S3Client<P, D>
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
         request.set_payload(input.body);
+        request.set_payload_stream(input.body_stream.as_ref());
 
-        let mut result = new_sign_and_execute(&self.dispatcher,
-                                          &mut request,
-                                          operation,
-                                          try!(self.credentials_provider.credentials()));
+        let mut result = try!(self.new_sign_and_execute(&mut request, operation));
         let status = result.status;
 
         match status {
             200 => {
                 let mut put_result = PutObjectOutput::default();
+                put_result.e_tag = try!(S3Client::<P,D>::get_value_for_header("ETag".to_string(), &result));
+                put_result.version_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-version-id".to_string(), &result));
+                put_result.expiration = try!(S3Client::<P,D>::get_value_for_header("x-amz-expiration".to_string(), &result));
+                put_result.server_side_encryption = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption".to_string(), &result));
+                put_result.ssekms_key_id = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-aws-kms-key-id".to_string(), &result));
+                put_result.request_charged = try!(S3Client::<P,D>::get_value_for_header("x-amz-request-charged".to_string(), &result));
+                put_result.response_metadata = ResponseMetadata::from_response(&result);
+                put_result.sse_customer_algorithm = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-customer-algorithm".to_string(), &result));
+                put_result.sse_customer_key_md5 = try!(S3Client::<P,D>::get_value_for_header("x-amz-server-side-encryption-customer-key-MD5".to_string(), &result));
 
                 Ok(put_result)
             },
             _ => {
-                let mut reader = EventReader::from_str(&result.body);
+                let mut reader = EventReader::new(&result.body[..]);
                 let mut stack = XmlResponse::new(reader.into_iter().peekable());
                 stack.next(); // xml start tag
 
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error putting object", aws))
+                Err(S3Error::with_status("Error putting object", status, aws))
             },
         }
     }
 
     /// uses the acl subresource to set the access control list (ACL) permissions for
     /// an object that already exists in a bucket
-    pub fn put_object_acl(&self, input: &PutObjectAclRequest) -> Result<(), S3Error> {
+    pub fn put_object_acl(&self, input: &PutObjectAclRequest) -> Result<PutObjectAclOutput, S3Error> {
+        let policy: Vec<u8>;
         let mut path: String;
         if self.endpoint.signature == Signature::V2 {
             path = format!("/{}?acl", input.key);
@@ -1976,7 +3048,7 @@ impl<P, D> S3Client<P, D>
 
         let mut request = SignedRequest::new("PUT",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
                                              &path,
                                              &self.endpoint);
@@ -1989,28 +3061,42 @@ impl<P, D> S3Client<P, D>
             request.set_params(params);
         }
 
-        // Not doing anything but allow unused_variables is set above to kill warning.
-        let acls = build_object_acls(&mut request, &input);
+        try!(build_object_acls(&mut request, &input));
+
+        if let Some(ref access_control_policy) = input.access_control_policy {
+            policy = access_control_policy_xml(access_control_policy);
+            match input.content_md5 {
+                Some(ref md5) => request.add_header("Content-MD5", md5),
+                None => request.add_header("Content-MD5", &content_md5(&policy)),
+            }
+            request.set_content_type("application/xml".to_string());
+            request.set_payload(Some(&policy));
+        }
+
+        if let Some(ref request_payer) = input.request_payer {
+            request.add_header("x-amz-request-payer", request_payer);
+        }
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
         match status {
             200 => {
-                // No response on acl puts
-                Ok(())
+                // No response body on acl puts
+                Ok(PutObjectAclOutput {
+                    request_charged: try!(S3Client::<P,D>::get_value_for_header("x-amz-request-charged".to_string(), &result)),
+                    response_metadata: ResponseMetadata::from_response(&result),
+                })
             }
             _ => {
-                let mut reader = EventReader::from_str(&result.body);
+                let mut reader = EventReader::new(&result.body[..]);
                 let mut stack = XmlResponse::new(reader.into_iter().peekable());
                 stack.next(); // xml start tag
 
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error putting object acl", aws))
+                Err(S3Error::with_status("Error putting object acl", status, aws))
             }
         }
     }
@@ -2019,42 +3105,115 @@ impl<P, D> S3Client<P, D>
     pub fn list_object_versions(&self, input: &ListObjectVersionsRequest) -> Result<ListVersionsResult, S3Error> {
         let mut request = SignedRequest::new("GET",
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              &input.bucket,
-                                             if self.endpoint.signature == Signature::V2 {"/?versions"} else {"/"},
+                                             "/",
                                              &self.endpoint);
 
-        if self.endpoint.signature == Signature::V4 {
-            let mut params = Params::new();
-            params.put("versions", "");
-            // params.put("Action", "ListObjectVersions");
-            // ListObjectVersionsRequestWriter::write_params(&mut params, "", input);
-            request.set_params(params);
-        }
+        // NOTE: The sub-resource is a param for V2 too since the markers are added as params.
+        let mut params = Params::new();
+        params.put("versions", "");
+        ListObjectVersionsRequestWriter::write_params(&mut params, "", input);
+        request.set_params(params);
 
         let hostname = self.hostname(Some(&input.bucket));
         request.set_hostname(Some(hostname));
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
-        let mut reader = EventReader::from_str(&result.body);
+        let mut reader = EventReader::new(&result.body[..]);
         let mut stack = XmlResponse::new(reader.into_iter().peekable());
 
         stack.next(); // xml start tag
 
         match status {
             200 => {
-                Ok(try!(ListVersionsResultParser::parse_xml("ListVersionsResult", &mut stack)))
+                let mut output = try!(ListVersionsResultParser::parse_xml("ListVersionsResult", &mut stack));
+                output.response_metadata = ResponseMetadata::from_response(&result);
+                Ok(output)
             },
             _ => {
                 let aws = try!(AWSError::parse_xml("Error", &mut stack));
-                Err(S3Error::with_aws("Error listing object versions", aws))
+                Err(S3Error::with_status("Error listing object versions", status, aws))
             },
         }
     }
 
+    /// Returns an iterator over every object version and delete marker in a bucket.
+    pub fn list_object_versions_iter<'a>(&'a self, input: &ListObjectVersionsRequest) -> ListObjectVersionsIterator<'a, P, D> {
+        ListObjectVersionsIterator::new(self, input)
+    }
+
+    /// Returns a presigned URL that grants temporary access to an object without credentials.
+    /// The URL is valid for `expires` seconds (at most 7 days for V4). V4 URLs use an unsigned
+    /// payload and V2 URLs use the `Expires` time.
+    ///
+    /// `headers` are signed and must be sent with the request (e.g. Content-Type for a PUT).
+    /// `params` are added to the query string such as `versionId` or `response-content-type`.
+    pub fn presign_url(&self,
+                       method: &str,
+                       bucket: &str,
+                       key: &str,
+                       expires: u32,
+                       headers: Option<&HashMap<String, String>>,
+                       params: Option<&Params>)
+                       -> Result<String, S3Error> {
+        if expires < 1 {
+            return Err(S3Error::new("Presigned URL must expire at least 1 second from now"));
+        }
+        if self.endpoint.signature == Signature::V4 && expires > 604800 {
+            return Err(S3Error::new("Presigned URL can't expire more than 7 days (604800 seconds) from now"));
+        }
+
+        let path = format!("/{}", key);
+        let mut request = SignedRequest::new(&method.to_uppercase(),
+                                             "s3",
+                                             self.region.clone(),
+                                             bucket,
+                                             &path,
+                                             &self.endpoint);
+
+        let hostname = self.hostname(Some(&bucket.to_string()));
+        request.set_hostname(Some(hostname));
+
+        if let Some(params) = params {
+            request.set_params(params.clone());
+        }
+        if let Some(headers) = headers {
+            for (key, value) in headers.iter() {
+                request.add_header(key, value);
+            }
+        }
+
+        Ok(request.presign(&try!(self.credentials_provider.credentials()), expires))
+    }
+
+    /// Signs a `PostPolicy` for browser based uploads and returns the Url and form fields to use.
+    /// The policy is signed with the signature version of the `Endpoint`.
+    pub fn post_policy_form(&self, policy: &PostPolicy) -> Result<PostPolicyForm, S3Error> {
+        let mut form = try!(policy.sign(&try!(self.credentials_provider.credentials()),
+                                        self.region.clone(),
+                                        &self.endpoint.signature));
+
+        let (scheme, port) = match self.endpoint.endpoint {
+            Some(ref url) => (url.scheme().to_string(), url.port()),
+            None => ("https".to_string(), None),
+        };
+        let hostname = self.hostname(Some(&policy.bucket));
+        let path = if hostname.starts_with(&format!("{}.", policy.bucket)) {
+            "/".to_string()
+        } else {
+            format!("/{}/", policy.bucket)
+        };
+
+        form.url = match port {
+            Some(port) => format!("{}://{}:{}{}", scheme, hostname, port, path),
+            None => format!("{}://{}{}", scheme, hostname, path),
+        };
+
+        Ok(form)
+    }
+
     // NB: This section is only for Ceph RGW Admin. It may be moved into it's own library later.
     //
     /// This method is for Ceph RGW Admin requests only. It performs the base Admin operations
@@ -2092,7 +3251,7 @@ impl<P, D> S3Client<P, D>
 
         let mut request = SignedRequest::new(&method,
                                              "s3",
-                                             self.region,
+                                             self.region.clone(),
                                              "",
                                              &format!("/{}",
                                                       input.admin_path.unwrap_or("".to_string())),
@@ -2107,9 +3266,7 @@ impl<P, D> S3Client<P, D>
         request.set_params(params);
         request.set_hostname(self.endpoint.hostname());
 
-        let result = sign_and_execute(&self.dispatcher,
-                                      &mut request,
-                                      try!(self.credentials_provider.credentials()));
+        let result = try!(self.sign_and_execute(&mut request));
         let status = result.status;
 
         match status {
@@ -2118,7 +3275,7 @@ impl<P, D> S3Client<P, D>
                 if result.body.is_empty() {
                     body = "{}".to_string();
                 } else {
-                    body = result.body;
+                    body = result.body_str().into_owned();
                 }
                 let mut admin_output = AdminOutput{
                     code: status as u16,
@@ -2134,25 +3291,252 @@ impl<P, D> S3Client<P, D>
     }
 
 
-    // Internal hostname method - Checks for buckets names with '.' in it.
+    // Internal hostname method - Falls back to path style when the bucket can't be virtual.
     fn hostname(&self, bucket: Option<&BucketName>) -> String {
         match bucket {
-            Some(b) => {
-                // NOTE: If the bucket name contains '.' then it must follow path vs virtual bucket
-                if b.contains(".") || !self.endpoint.is_bucket_virtual {
-                    format!("{}", self.endpoint.hostname().unwrap())
-                } else {
-                    format!("{}.{}", b, self.endpoint.hostname().unwrap())
-                }
-            },
+            Some(b) => self.endpoint.bucket_hostname(b, &self.endpoint.hostname().unwrap()),
             None => format!("{}", self.endpoint.hostname().unwrap()),
         }
     }
+
+    // Signs and sends the request. See `execute`.
+    fn sign_and_execute(&self, signed_request: &mut SignedRequest) -> Result<HttpResponse, S3Error> {
+        let mut retries = 0;
+        self.execute(signed_request, &mut retries, &mut None)
+    }
+
+    // Internal method that calls the hyper dispatcher to send the URL request.
+    fn new_sign_and_execute(&self,
+                            signed_request: &mut SignedRequest,
+                            operation: Option<&mut Operation>)
+        -> Result<HttpResponse, S3Error>
+    {
+        self.execute_operation(signed_request, operation, &mut None)
+    }
+
+    // Sends the request and records it on the operation. The body of a successful response is
+    // written to the sink if there is one.
+    fn execute_operation(&self,
+                         signed_request: &mut SignedRequest,
+                         operation: Option<&mut Operation>,
+                         sink: &mut Option<&mut BodySink>)
+        -> Result<HttpResponse, S3Error>
+    {
+        let response: HttpResponse;
+        let mut retries = 0;
+
+        // NOTE: May want to move to request.rs in dispatcher method instead of here. That would be
+        // the lowest level before hyper library. We can actually pull in our own version of hyper
+        // since this is a binary and then add time options at the tcp level to measure first byte
+        // for latency but we're only interested in throughput and not latency.
+        // Latency - Duration from start to first byte.
+        // Throughput - Total number of full requests / total time.
+        //
+        if let Some(op) = operation {
+            let endpoint = match signed_request.endpoint.endpoint {
+                Some(ref url) => url.clone().into_string(),
+                None => String::new(),
+            };
+            op.object = format!("{}", signed_request.path);
+            op.method = signed_request.method.clone();
+            op.request = format!("{}{}{}", endpoint, signed_request.bucket, signed_request.path);
+            op.endpoint = endpoint;
+            if op.method.to_lowercase() == "put" {
+                op.payload_size = match signed_request.payload_stream {
+                    Some(stream) => stream.len(),
+                    None => signed_request.payload.map_or(0, |payload| payload.len() as u64),
+                };
+            }
+
+            let start_time = UTC::now();
+            let now = Instant::now();
+
+            // NB: The duration includes the retries and the delays between them.
+            let result = self.execute(signed_request, &mut retries, sink);
+            op.retries = retries;
+            response = try!(result);
+
+            let duration = now.elapsed();
+            op.duration = Some(duration);
+            op.end_time = chrono::Duration::from_std(duration).ok().map(|duration| start_time + duration);
+            op.start_time = Some(start_time);
+
+            if op.method.to_lowercase() != "put" {
+                op.payload_size = match *sink {
+                    Some(ref sink) => sink.written(),
+                    None => response.body.len() as u64,
+                };
+            }
+            op.success = if response.status < 400 {true} else {false}; //Do more here later...
+            op.code = response.status;
+        } else {
+            response = try!(self.execute(signed_request, &mut retries, sink));
+        }
+
+        Ok(response)
+    }
+
+    // Sends the request with the RetryPolicy of the client. Requests for a bucket on AWS are sent
+    // to the region of the bucket if it is cached. If S3 answers that the bucket is in another
+    // region (301 PermanentRedirect or 400 AuthorizationHeaderMalformed) the region is taken from
+    // the x-amz-bucket-region header, the error XML or a HEAD bucket, cached and the request is
+    // signed for that region and sent again.
+    fn execute(&self,
+               signed_request: &mut SignedRequest,
+               retries: &mut u32,
+               sink: &mut Option<&mut BodySink>)
+        -> Result<HttpResponse, S3Error>
+    {
+        let bucket = signed_request.bucket.clone();
+        let discover = !bucket.is_empty() && self.endpoint.is_aws();
+
+        if discover {
+            match self.cached_bucket_region(&bucket) {
+                Some(region) if region != signed_request.region => self.set_request_region(signed_request, region),
+                _ => {},
+            }
+        }
+
+        let response = try!(execute_with_retries(&self.dispatcher,
+                                                 signed_request,
+                                                 &self.credentials_provider,
+                                                 &self.retry_policy,
+                                                 retries,
+                                                 sink));
+        if !discover || !is_wrong_region_response(&response) || !signed_request.is_replayable() {
+            return Ok(response);
+        }
+
+        let region = match bucket_region_from_response(&response) {
+            Some(region) => Some(region),
+            None => self.head_bucket_region(&bucket).ok(),
+        };
+        match region {
+            Some(region) if region != signed_request.region => {
+                debug!("Bucket {} is in region {}, resending request.", bucket, region);
+                self.cache_bucket_region(&bucket, &region);
+                self.set_request_region(signed_request, region);
+
+                let mut region_retries = 0;
+                let result = execute_with_retries(&self.dispatcher,
+                                                  signed_request,
+                                                  &self.credentials_provider,
+                                                  &self.retry_policy,
+                                                  &mut region_retries,
+                                                  sink);
+                *retries += 1 + region_retries;
+                result
+            },
+            _ => Ok(response),
+        }
+    }
+
+    /// Returns the region of a bucket. The region is cached so only the first call for a given
+    /// bucket sends a HEAD bucket request. Third party (non AWS) endpoints always return the
+    /// region of the client.
+    pub fn bucket_region(&self, bucket: &str) -> Result<Region, S3Error> {
+        if !self.endpoint.is_aws() {
+            return Ok(self.region.clone());
+        }
+        if let Some(region) = self.cached_bucket_region(bucket) {
+            return Ok(region);
+        }
+
+        let region = try!(self.head_bucket_region(bucket));
+        self.cache_bucket_region(bucket, &region);
+        Ok(region)
+    }
+
+    // Sends a HEAD bucket without retries and reads the x-amz-bucket-region header. S3
+    // returns the header even when the request is for the wrong region or isn't authorized.
+    fn head_bucket_region(&self, bucket: &str) -> Result<Region, S3Error> {
+        let mut request = SignedRequest::new("HEAD",
+                                             "s3",
+                                             self.region.clone(),
+                                             bucket,
+                                             "/",
+                                             &self.endpoint);
+        request.set_hostname(Some(self.hostname(Some(&bucket.to_string()))));
+
+        let creds = try!(self.credentials_provider.credentials());
+        request.sign(&creds);
+        let response = try!(dispatch_with_redirects(&self.dispatcher, &mut request, &creds, &mut None));
+
+        match bucket_region_from_response(&response) {
+            Some(region) => Ok(region),
+            None => Err(S3Error::with_status(format!("Couldn't find the region of bucket {}", bucket),
+                                             response.status,
+                                             AWSError::default())),
+        }
+    }
+
+    fn cached_bucket_region(&self, bucket: &str) -> Option<Region> {
+        match self.bucket_regions.lock() {
+            Ok(bucket_regions) => bucket_regions.get(bucket).cloned(),
+            Err(_) => None,
+        }
+    }
+
+    fn cache_bucket_region(&self, bucket: &str, region: &Region) {
+        if let Ok(mut bucket_regions) = self.bucket_regions.lock() {
+            bucket_regions.insert(bucket.to_string(), region.clone());
+        }
+    }
+
+    // Points the request to the regional endpoint of the region and signs it for that region.
+    fn set_request_region(&self, signed_request: &mut SignedRequest, region: Region) {
+        let hostname = s3_hostname(&region, &self.endpoint.options);
+        let hostname = self.endpoint.bucket_hostname(&signed_request.bucket, &hostname);
+        signed_request.region = region;
+        signed_request.set_hostname(Some(hostname));
+    }
+}
+
+// NOTE: V2 - The tagging and versionId sub-resources are part of the path.
+fn object_tagging_path(signature: &Signature, key: &str, version_id: &Option<ObjectVersionId>) -> String {
+    if *signature == Signature::V2 {
+        match *version_id {
+            Some(ref version_id) => format!("/{}?tagging&versionId={}", key, version_id),
+            None => format!("/{}?tagging", key),
+        }
+    } else {
+        format!("/{}", key)
+    }
+}
+
+// NOTE: V4 - The tagging and versionId sub-resources are params.
+fn object_tagging_params(version_id: &Option<ObjectVersionId>) -> Params {
+    let mut params = Params::new();
+    params.put("tagging", "");
+    if let Some(ref version_id) = *version_id {
+        params.put("versionId", version_id);
+    }
+    params
+}
+
+// Returns the x-amz-copy-source value which is the percent encoded bucket/key (the '/' separators
+// are left intact) followed by the versionId if a specific version is copied.
+fn copy_source(copy_source: &str, version_id: &Option<CopySourceVersionId>) -> String {
+    match *version_id {
+        Some(ref version_id) => format!("{}?versionId={}", byte_serialize(copy_source, false), version_id),
+        None => byte_serialize(copy_source, false),
+    }
 }
 
 /// `extract_s3_redirect_location` takes a Hyper `Response` and attempts to pull out the temporary endpoint.
 fn extract_s3_redirect_location(response: HttpResponse) -> Result<String, S3Error> {
-    let mut reader = EventReader::from_str(&response.body);
+    // HEAD responses have no body so the host of the Location header is used instead.
+    if response.body_str().trim().is_empty() {
+        let location = response.headers.iter()
+                               .find(|&(name, _)| name.eq_ignore_ascii_case("location"))
+                               .and_then(|(_, value)| Url::parse(value).ok());
+        return match location.and_then(|url| url.host_str().map(|host| host.to_string())) {
+            Some(host) => Ok(host),
+            None => Err(S3Error::with_status("Couldn't find redirect location for S3 bucket", response.status, AWSError::default())),
+        };
+    }
+
+    let mut reader = EventReader::new(&response.body[..]);
     let mut stack = XmlResponse::new(reader.into_iter().peekable());
     stack.next(); // xml start tag
 
@@ -2191,132 +3575,531 @@ fn extract_s3_temporary_endpoint_from_xml<T: Peek + Next>(stack: &mut T) -> Resu
     Err(S3Error::new("Couldn't find redirect location for S3 bucket"))
 }
 
-fn sign_and_execute<D>(dispatcher: &D,
-                       signed_request: &mut SignedRequest,
-                       creds: AwsCredentials)
-    -> HttpResponse
-    where D: DispatchSignedRequest,
-{
-    signed_request.sign(&creds);
+// Maximum number of 307 (temporary redirect) responses followed for a single request.
+const MAX_REDIRECTS: usize = 5;
 
-    let response = dispatcher.dispatch(signed_request).expect("Error dispatching request");
-
-    if response.status == 307 {
-        debug!("Got a redirect response, resending request.");
-        // extract location from response, modify request and re-sign and resend.
-        let new_hostname = extract_s3_redirect_location(response).unwrap();
-        signed_request.set_hostname(Some(new_hostname.to_string()));
+// True if S3 rejected the request because the bucket is in another region.
+fn is_wrong_region_response(response: &HttpResponse) -> bool {
+    match response.status {
+        301 => true,
+        400 => response.headers.keys().any(|name| name.eq_ignore_ascii_case("x-amz-bucket-region")) ||
+               response.body_str().contains("AuthorizationHeaderMalformed"),
+        _ => false,
+    }
+}
 
-        // This does a lot of appending and not clearing/creation, so we'll have to do that ourselves:
-        signed_request.sign(&creds);
-        return dispatcher.dispatch(signed_request).unwrap();
+// Region of the bucket from the x-amz-bucket-region header or the Region element of the error XML.
+fn bucket_region_from_response(response: &HttpResponse) -> Option<Region> {
+    for (name, value) in response.headers.iter() {
+        if name.eq_ignore_ascii_case("x-amz-bucket-region") {
+            return Region::from_str(value).ok();
+        }
     }
+    if response.body_str().trim().is_empty() {
+        return None;
+    }
+
+    let mut reader = EventReader::new(&response.body[..]);
+    let mut stack = XmlResponse::new(reader.into_iter().peekable());
+    stack.next(); // xml start tag
 
-    response
+    match AWSError::parse_xml("Error", &mut stack) {
+        Ok(aws) => Region::from_str(&aws.region).ok(),
+        Err(_) => None,
+    }
 }
 
-// Internal method that calls the hyper dispatcher to send the URL request.
-fn new_sign_and_execute<D>(dispatcher: &D,
-                       signed_request: &mut SignedRequest,
-                       operation: Option<&mut Operation>,
-                       creds: AwsCredentials)
-    -> HttpResponse
-    where D: DispatchSignedRequest,
+// Signs and sends the request until it succeeds, fails with an error the RetryPolicy doesn't
+// retry or runs out of attempts. Each attempt gets fresh credentials and a new request date.
+// `retries` is set to the number of attempts after the first one.
+fn execute_with_retries<P, D>(dispatcher: &D,
+                              signed_request: &mut SignedRequest,
+                              credentials_provider: &P,
+                              retry_policy: &RetryPolicy,
+                              retries: &mut u32,
+                              sink: &mut Option<&mut BodySink>)
+    -> Result<HttpResponse, S3Error>
+    where P: AwsCredentialsProvider,
+          D: DispatchSignedRequest,
 {
-    signed_request.sign(&creds);
-    let response: HttpResponse;
-
-    // NOTE: May want to move to request.rs in dispatcher method instead of here. That would be
-    // the lowest level before hyper library. We can actually pull in our own version of hyper
-    // since this is a binary and then add time options at the tcp level to measure first byte
-    // for latency but we're only interested in throughput and not latency.
-    // Latency - Duration from start to first byte.
-    // Throughput - Total number of full requests / total time.
-    //
-    if let Some(op) = operation {
-        op.object = format!("{}", signed_request.path);
-        op.method = signed_request.method.clone();
-        op.request = format!("{}{}{}",
-                     signed_request.endpoint.clone().endpoint.unwrap().into_string(),
-                     signed_request.bucket,
-                     signed_request.path);
-        op.endpoint = signed_request.endpoint.clone().endpoint.unwrap().into_string();
-        if op.method.to_lowercase() == "put" {
-            op.payload_size = signed_request.payload.unwrap().len() as u64;
-        }
+    let mut attempt = 0;
 
-        let start_time = UTC::now();
-        let now = Instant::now();
-
-        response = dispatcher.dispatch(signed_request).expect("Error dispatching timed request");
+    loop {
+        let creds = try!(credentials_provider.credentials());
+        signed_request.sign(&creds);
 
-        op.duration = Some(now.elapsed());
-        op.end_time = Some(start_time + chrono::Duration::from_std(op.duration.unwrap()).unwrap());
-        op.start_time = Some(start_time);
+        let result = dispatch_with_redirects(dispatcher, signed_request, &creds, sink);
+        let retry = match result {
+            Ok(ref response) => retry_policy.should_retry_response(response),
+            Err(ref err) => retry_policy.should_retry_error(err),
+        };
 
-        if op.method.to_lowercase() != "put" {
-            op.payload_size = response.body.len() as u64;
+        // NB: A streamed payload was consumed by the attempt so it can't be sent again and a
+        // sink that already got part of a body can't take it again.
+        let written = sink.as_ref().map_or(0, |sink| sink.written());
+        if !retry || attempt + 1 >= retry_policy.max_attempts || !signed_request.is_replayable() || written > 0 {
+            *retries = attempt;
+            return result;
         }
-        op.success = if response.status < 400 {true} else {false}; //Do more here later...
-        op.code = response.status;
-    } else {
-        response = dispatcher.dispatch(signed_request).expect("Error dispatching request");
+
+        let delay = retry_policy.delay(attempt);
+        debug!("Retrying request {} after {:?} (attempt {} of {}).",
+               signed_request.path, delay, attempt + 2, retry_policy.max_attempts);
+        thread::sleep(delay);
+        attempt += 1;
     }
+}
+
+// Sends an already signed request. A 307 response means the bucket is temporarily served by
+// another endpoint so the request is re-signed and resent there, up to MAX_REDIRECTS times.
+// Requests with a streamed payload are not redirected since the stream was already read.
+fn dispatch_with_redirects<D>(dispatcher: &D,
+                              signed_request: &mut SignedRequest,
+                              creds: &AwsCredentials,
+                              sink: &mut Option<&mut BodySink>)
+    -> Result<HttpResponse, S3Error>
+    where D: DispatchSignedRequest,
+{
+    let mut response = try!(dispatch(dispatcher, signed_request, sink));
+    let mut redirects = 0;
+
+    while response.status == 307 && signed_request.is_replayable() {
+        if redirects == MAX_REDIRECTS {
+            return Err(S3Error::with_status(format!("Too many redirects ({}) for request", MAX_REDIRECTS),
+                                            response.status,
+                                            AWSError::default()));
+        }
+        redirects += 1;
 
-    if response.status == 307 {
         debug!("Got a redirect response, resending request.");
         // extract location from response, modify request and re-sign and resend.
-        let new_hostname = extract_s3_redirect_location(response).unwrap();
-        signed_request.set_hostname(Some(new_hostname.to_string()));
+        let new_hostname = try!(extract_s3_redirect_location(response));
+        signed_request.set_hostname(Some(new_hostname));
 
         // This does a lot of appending and not clearing/creation, so we'll have to do that ourselves:
-        signed_request.sign(&creds);
-        return dispatcher.dispatch(signed_request).unwrap();
+        signed_request.sign(creds);
+        response = try!(dispatch(dispatcher, signed_request, sink));
     }
 
-    response
+    Ok(response)
+}
+
+// Sends the request once. The body of a successful response goes to the sink if there is one.
+fn dispatch<D>(dispatcher: &D,
+               signed_request: &SignedRequest,
+               sink: &mut Option<&mut BodySink>)
+    -> Result<HttpResponse, S3Error>
+    where D: DispatchSignedRequest,
+{
+    let response = match *sink {
+        Some(ref mut sink) => try!(dispatcher.dispatch_to_writer(signed_request, *sink)),
+        None => try!(dispatcher.dispatch(signed_request)),
+    };
+    Ok(response)
 }
 
-// Builds the bucket acl header
+// Builds the bucket acl headers
 fn build_bucket_acls(request: &mut SignedRequest, input: &PutBucketAclRequest) -> Result<(), S3Error> {
+    let has_headers = input.acl.is_some() || input.grant_read.is_some() || input.grant_write.is_some() ||
+                      input.grant_read_acp.is_some() || input.grant_write_acp.is_some() ||
+                      input.grant_full_control.is_some();
+    if has_headers && input.access_control_policy.is_some() {
+        return Err(S3Error::new("Use either an access control policy or acl/grant headers but not both"));
+    }
+
     match input.acl {
         Some(ref canned_acl) => request.add_header("x-amz-acl", &canned_acl_in_aws_format(canned_acl)),
         None => {},
     }
-    // match input.grant_write {
-    // Some(ref grants) => {
-    // let grant_str: String = String::new();
-    // Cycle through
-    // for grant in grants {
-    //
-    // }
-    //
-    // request.add_header("x-amz-grant-write", ""),
-    // }
-    // None => {},
-    // }
-    //
+    add_grant_header(request, "x-amz-grant-read", &input.grant_read);
+    add_grant_header(request, "x-amz-grant-write", &input.grant_write);
+    add_grant_header(request, "x-amz-grant-read-acp", &input.grant_read_acp);
+    add_grant_header(request, "x-amz-grant-write-acp", &input.grant_write_acp);
+    add_grant_header(request, "x-amz-grant-full-control", &input.grant_full_control);
     Ok(())
 }
 
-// Builds the object acl header
+// Builds the object acl headers
 fn build_object_acls(request: &mut SignedRequest, input: &PutObjectAclRequest) -> Result<(), S3Error> {
+    let has_headers = input.acl.is_some() || input.grant_read.is_some() || input.grant_write.is_some() ||
+                      input.grant_read_acp.is_some() || input.grant_write_acp.is_some() ||
+                      input.grant_full_control.is_some();
+    if has_headers && input.access_control_policy.is_some() {
+        return Err(S3Error::new("Use either an access control policy or acl/grant headers but not both"));
+    }
+
     match input.acl {
         Some(ref canned_acl) => request.add_header("x-amz-acl", &canned_acl_in_aws_format(canned_acl)),
         None => {},
     }
-    // match input.grant_write {
-    // Some(ref grants) => {
-    // let grant_str: String = String::new();
-    // Cycle through
-    // for grant in grants {
-    //
-    // }
-    //
-    // request.add_header("x-amz-grant-write", ""),
-    // }
-    // None => {},
-    // }
-    //
+    add_grant_header(request, "x-amz-grant-read", &input.grant_read);
+    add_grant_header(request, "x-amz-grant-write", &input.grant_write);
+    add_grant_header(request, "x-amz-grant-read-acp", &input.grant_read_acp);
+    add_grant_header(request, "x-amz-grant-write-acp", &input.grant_write_acp);
+    add_grant_header(request, "x-amz-grant-full-control", &input.grant_full_control);
     Ok(())
 }
+
+// Adds the If-* headers of a conditional GET or HEAD.
+fn add_conditional_headers(request: &mut SignedRequest,
+                           if_match: &Option<IfMatch>,
+                           if_none_match: &Option<IfNoneMatch>,
+                           if_modified_since: &Option<IfModifiedSince>,
+                           if_unmodified_since: &Option<IfUnmodifiedSince>) {
+    if let Some(ref etag) = *if_match {
+        request.add_header("If-Match", etag);
+    }
+    if let Some(ref etag) = *if_none_match {
+        request.add_header("If-None-Match", etag);
+    }
+    if let Some(ref since) = *if_modified_since {
+        request.add_header("If-Modified-Since", since);
+    }
+    if let Some(ref since) = *if_unmodified_since {
+        request.add_header("If-Unmodified-Since", since);
+    }
+}
+
+// A 304 (not modified) or 412 (precondition failed) response to a conditional request. 304 and
+// HEAD responses have no body so the error code is set from the status when there is no XML.
+fn conditional_request_error(response: &HttpResponse) -> S3Error {
+    let (code, message) = match response.status {
+        304 => ("NotModified", "Not Modified"),
+        _ => ("PreconditionFailed", "At least one of the preconditions you specified did not hold"),
+    };
+
+    let mut aws = AWSError::default();
+    if !response.body_str().trim().is_empty() {
+        let mut reader = EventReader::new(&response.body[..]);
+        let mut stack = XmlResponse::new(reader.into_iter().peekable());
+        stack.next(); // xml start tag
+
+        if let Ok(parsed) = AWSError::parse_xml("Error", &mut stack) {
+            aws = parsed;
+        }
+    }
+    if aws.code.is_empty() {
+        aws.code = code.to_string();
+        aws.message = message.to_string();
+    }
+    if aws.request_id.is_empty() {
+        if let Some(request_id) = response.headers.get("x-amz-request-id") {
+            aws.request_id = request_id.to_string();
+        }
+    }
+
+    S3Error::with_status(message, response.status, aws)
+}
+
+// Adds an x-amz-grant-* header when there is at least one grantee
+fn add_grant_header(request: &mut SignedRequest, name: &str, grantees: &Option<HeaderGrantees>) {
+    if let Some(ref grantees) = *grantees {
+        if !grantees.is_empty() {
+            request.add_header(name, &grant_header_value(grantees));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use aws::common::body::StreamingBody;
+    use aws::common::credentials::ParametersProvider;
+    use aws::common::request::{HttpDispatchError, request_uri};
+
+    // Answers with the given responses in order and then keeps repeating the last one.
+    struct ScriptedDispatcher {
+        responses: RefCell<Vec<HttpResponse>>,
+        hostnames: RefCell<Vec<String>>,
+        uris: RefCell<Vec<String>>,
+    }
+
+    impl DispatchSignedRequest for ScriptedDispatcher {
+        fn dispatch(&self, request: &SignedRequest) -> Result<HttpResponse, HttpDispatchError> {
+            self.hostnames.borrow_mut().push(request.hostname());
+            self.uris.borrow_mut().push(request_uri(request).unwrap());
+            let mut responses = self.responses.borrow_mut();
+            if responses.len() > 1 {
+                Ok(responses.remove(0))
+            } else {
+                let response = &responses[0];
+                Ok(HttpResponse { status: response.status,
+                                  body: response.body.clone(),
+                                  headers: response.headers.clone() })
+            }
+        }
+    }
+
+    fn response(status: u16, header: Option<(&str, &str)>) -> HttpResponse {
+        let mut response = HttpResponse::default();
+        response.status = status;
+        if let Some((name, value)) = header {
+            response.headers.insert(name.to_string(), value.to_string());
+        }
+        response
+    }
+
+    fn client(responses: Vec<HttpResponse>) -> S3Client<ParametersProvider, ScriptedDispatcher> {
+        client_with_signature(responses, Signature::V4)
+    }
+
+    fn client_with_signature(responses: Vec<HttpResponse>, signature: Signature)
+                             -> S3Client<ParametersProvider, ScriptedDispatcher> {
+        let endpoint = Endpoint::new(Region::UsEast1, signature, None, None, None, None);
+        let dispatcher = ScriptedDispatcher { responses: RefCell::new(responses),
+                                              hostnames: RefCell::new(Vec::new()),
+                                              uris: RefCell::new(Vec::new()) };
+        let provider = ParametersProvider::with_parameters("access", "secret", None).unwrap();
+        let mut client = S3Client::with_request_dispatcher(dispatcher, provider, endpoint);
+        let mut policy = RetryPolicy::default();
+        policy.base_delay = Duration::from_millis(0);
+        client.set_retry_policy(policy);
+        client
+    }
+
+    #[test]
+    fn redirects_are_bounded() {
+        let client = client(vec![response(307, Some(("Location", "https://bucket.s3-us-west-2.amazonaws.com/key")))]);
+        let mut request = SignedRequest::new("HEAD", "s3", Region::UsEast1, "bucket", "/key", &client.endpoint);
+
+        let err = client.sign_and_execute(&mut request).unwrap_err();
+        assert_eq!(err.status, 307);
+        assert_eq!(err.kind, S3ErrorKind::Unknown(String::new()));
+        assert_eq!(client.dispatcher.hostnames.borrow().len(), MAX_REDIRECTS + 1);
+        assert_eq!(request.hostname(), "bucket.s3-us-west-2.amazonaws.com");
+    }
+
+    #[test]
+    fn retries_are_recorded_on_operation() {
+        let client = client(vec![response(503, None), response(500, None), response(200, None)]);
+        let mut request = SignedRequest::new("GET", "s3", Region::UsEast1, "bucket", "/key", &client.endpoint);
+        let mut operation = Operation::default();
+
+        let result = client.new_sign_and_execute(&mut request, Some(&mut operation)).unwrap();
+        assert_eq!(result.status, 200);
+        assert_eq!(operation.retries, 2);
+        assert_eq!(operation.code, 200);
+    }
+
+    #[test]
+    fn bucket_region_is_discovered_and_cached() {
+        let client = client(vec![response(301, Some(("x-amz-bucket-region", "eu-west-1"))),
+                                 response(200, None)]);
+        let mut request = SignedRequest::new("GET", "s3", Region::UsEast1, "bucket", "/key", &client.endpoint);
+        request.set_hostname(Some(client.hostname(Some(&"bucket".to_string()))));
+
+        let result = client.sign_and_execute(&mut request).unwrap();
+        assert_eq!(result.status, 200);
+        assert_eq!(request.region, Region::EuWest1);
+        assert_eq!(*client.dispatcher.hostnames.borrow(),
+                   vec!["bucket.s3.amazonaws.com".to_string(), "bucket.s3.eu-west-1.amazonaws.com".to_string()]);
+
+        // The cached region is used from the first attempt on.
+        let mut request = SignedRequest::new("GET", "s3", Region::UsEast1, "bucket", "/other", &client.endpoint);
+        client.sign_and_execute(&mut request).unwrap();
+        assert_eq!(client.dispatcher.hostnames.borrow()[2], "bucket.s3.eu-west-1.amazonaws.com");
+        assert_eq!(client.bucket_region("bucket").unwrap(), Region::EuWest1);
+    }
+
+    #[test]
+    fn streamed_payloads_are_not_retried() {
+        let client = client(vec![response(503, None), response(200, None)]);
+        let body = StreamingBody::new(Cursor::new(vec![0u8; 16]), 16);
+        let mut request = SignedRequest::new("PUT", "s3", Region::UsEast1, "bucket", "/key", &client.endpoint);
+        request.set_payload_stream(Some(&body));
+
+        let result = client.sign_and_execute(&mut request).unwrap();
+        assert_eq!(result.status, 503);
+        assert_eq!(client.dispatcher.hostnames.borrow().len(), 1);
+        assert_eq!(request.get_header("x-amz-content-sha256"), "STREAMING-AWS4-HMAC-SHA256-PAYLOAD");
+        assert_eq!(request.get_header("x-amz-decoded-content-length"), "16");
+    }
+
+    #[test]
+    fn list_objects_iter_stops_after_last_page() {
+        let mut first = response(200, None);
+        first.body = b"<ListBucketResult><Name>bucket</Name><IsTruncated>true</IsTruncated>\
+                       <NextContinuationToken>page-2</NextContinuationToken>\
+                       <Contents><Key>a.txt</Key></Contents><Contents><Key>b.txt</Key></Contents>\
+                       </ListBucketResult>".to_vec();
+        let mut last = response(200, None);
+        last.body = b"<ListBucketResult><Name>bucket</Name><IsTruncated>false</IsTruncated>\
+                      <Contents><Key>c.txt</Key></Contents></ListBucketResult>".to_vec();
+        let client = client(vec![first, last]);
+
+        let mut request = ListObjectsRequest::default();
+        request.bucket = "bucket".to_string();
+        request.version = Some(2);
+
+        let keys: Vec<String> = client.list_objects_iter(&request).map(|o| o.unwrap().key).collect();
+        assert_eq!(keys, vec!["a.txt".to_string(), "b.txt".to_string(), "c.txt".to_string()]);
+        let uris = client.dispatcher.uris.borrow();
+        assert_eq!(uris.len(), 2);
+        assert!(!uris[0].contains("continuation-token"));
+        assert!(uris[1].contains("continuation-token=page-2"));
+    }
+
+    #[test]
+    fn list_object_versions_iter_keeps_document_order() {
+        let mut page = response(200, None);
+        page.body = b"<ListVersionsResult><Name>bucket</Name><IsTruncated>false</IsTruncated>\
+                      <DeleteMarker><Key>a.txt</Key><VersionId>3</VersionId>\
+                      <LastModified>2017-01-01T00:00:00.000Z</LastModified></DeleteMarker>\
+                      <Version><Key>a.txt</Key><VersionId>2</VersionId>\
+                      <LastModified>2017-01-01T00:00:00.000Z</LastModified></Version>\
+                      <DeleteMarker><Key>a.txt</Key><VersionId>1</VersionId>\
+                      <LastModified>2016-01-01T00:00:00.000Z</LastModified></DeleteMarker>\
+                      <Version><Key>b.txt</Key><VersionId>4</VersionId>\
+                      <LastModified>2015-01-01T00:00:00.000Z</LastModified></Version>\
+                      </ListVersionsResult>".to_vec();
+        let client = client(vec![page]);
+
+        let mut request = ListObjectVersionsRequest::default();
+        request.bucket = "bucket".to_string();
+
+        let entries: Vec<String> = client.list_object_versions_iter(&request)
+            .map(|entry| match entry.unwrap() {
+                ObjectVersionEntry::Version(version) => format!("version {}", version.version_id),
+                ObjectVersionEntry::DeleteMarker(marker) => format!("marker {}", marker.version_id),
+            })
+            .collect();
+        assert_eq!(entries, vec!["marker 3".to_string(), "version 2".to_string(),
+                                 "marker 1".to_string(), "version 4".to_string()]);
+    }
+
+    #[test]
+    fn config_outputs_carry_response_metadata() {
+        let mut tagging = response(200, Some(("x-amz-request-id", "get-id")));
+        tagging.body = b"<Tagging><TagSet><Tag><Key>project</Key><Value>blue</Value></Tag></TagSet></Tagging>".to_vec();
+        let client = client(vec![tagging, response(204, Some(("x-amz-request-id", "put-id")))]);
+
+        let output = client.get_bucket_tagging(&GetBucketTaggingRequest { bucket: "bucket".to_string() }).unwrap();
+        assert_eq!(output.tagging.tag_set[0].key, "project");
+        assert_eq!(output.response_metadata.request_id, "get-id");
+
+        let mut request = PutBucketTaggingRequest::default();
+        request.bucket = "bucket".to_string();
+        request.tagging = output.tagging;
+        let output = client.put_bucket_tagging(&request).unwrap();
+        assert_eq!(output.response_metadata.request_id, "put-id");
+    }
+
+    #[test]
+    fn v2_listing_sub_resources_are_params() {
+        let mut uploads = response(200, None);
+        uploads.body = b"<ListMultipartUploadsResult><Bucket>bucket</Bucket></ListMultipartUploadsResult>".to_vec();
+        let mut parts = response(200, None);
+        parts.body = b"<ListPartsResult><Bucket>bucket</Bucket></ListPartsResult>".to_vec();
+        let mut versions = response(200, None);
+        versions.body = b"<ListVersionsResult><Name>bucket</Name></ListVersionsResult>".to_vec();
+        let client = client_with_signature(vec![uploads, parts, versions], Signature::V2);
+
+        let mut request = MultipartUploadListRequest::default();
+        request.bucket = "bucket".to_string();
+        request.key_marker = Some("a.txt".to_string());
+        request.max_uploads = Some(2);
+        client.multipart_upload_list(&request).unwrap();
+
+        let mut request = MultipartUploadListPartsRequest::default();
+        request.bucket = "bucket".to_string();
+        request.key = "a.txt".to_string();
+        request.upload_id = "id".to_string();
+        request.part_number_marker = Some(3);
+        client.multipart_upload_list_parts(&request).unwrap();
+
+        let mut request = ListObjectVersionsRequest::default();
+        request.bucket = "bucket".to_string();
+        request.key_marker = Some("a.txt".to_string());
+        request.version_id_marker = Some("v1".to_string());
+        client.list_object_versions(&request).unwrap();
+
+        assert_eq!(*client.dispatcher.uris.borrow(),
+                   vec!["https://bucket.s3.amazonaws.com/?key-marker=a.txt&max-uploads=2&uploads=".to_string(),
+                        "https://bucket.s3.amazonaws.com/a.txt?part-number-marker=3&uploadId=id".to_string(),
+                        "https://bucket.s3.amazonaws.com/?key-marker=a.txt&version-id-marker=v1&versions=".to_string()]);
+    }
+
+    #[test]
+    fn copy_source_is_percent_encoded() {
+        assert_eq!(copy_source("bucket/photos/my summer/été.jpg", &None),
+                   "bucket/photos/my%20summer/%C3%A9t%C3%A9.jpg");
+        assert_eq!(copy_source("bucket/a+b&c.txt", &Some("v1".to_string())),
+                   "bucket/a%2Bb%26c.txt?versionId=v1");
+    }
+
+    #[test]
+    fn conditional_responses_map_to_error_kinds() {
+        let err = conditional_request_error(&response(304, Some(("x-amz-request-id", "304-id"))));
+        assert_eq!(err.status, 304);
+        assert_eq!(err.kind, S3ErrorKind::NotModified);
+        assert_eq!(err.aws.code, "NotModified");
+        assert_eq!(err.aws.request_id, "304-id");
+
+        let mut failed = response(412, None);
+        failed.body = b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+                        <Error><Code>PreconditionFailed</Code><Message>At least one of the pre-conditions you specified did not hold</Message>\
+                        <RequestId>412-id</RequestId></Error>".to_vec();
+        let err = conditional_request_error(&failed);
+        assert_eq!(err.status, 412);
+        assert_eq!(err.kind, S3ErrorKind::PreconditionFailed);
+        assert_eq!(err.aws.code, "PreconditionFailed");
+        assert_eq!(err.aws.request_id, "412-id");
+    }
+
+    #[test]
+    fn fingerprints_are_normalized() {
+        assert_eq!(normalize_fingerprint("AB:cd:0F:12"), "abcd0f12");
+        assert_eq!(normalize_fingerprint(" ab CD 0f "), "abcd0f");
+        assert_eq!(normalize_fingerprint("abcd0f"), "abcd0f");
+    }
+
+    #[test]
+    fn missing_tls_files_are_errors() {
+        let mut tls = TlsOptions::default();
+        tls.ca_file = Some(PathBuf::from("/nonexistent/ca.pem"));
+        let err = tls_client(&tls).err().expect("A missing CA file was not an error");
+        assert!(err.message.contains("CA file /nonexistent/ca.pem"));
+
+        let mut tls = TlsOptions::default();
+        tls.client_cert = Some(PathBuf::from("/nonexistent/client.pem"));
+        let err = tls_client(&tls).err().expect("A missing client certificate was not an error");
+        assert!(err.message.contains("client certificate /nonexistent/client.pem"));
+
+        let mut endpoint = Endpoint::new(Region::UsEast1, Signature::V4, None, None, None, None);
+        endpoint.tls = tls;
+        let provider = ParametersProvider::with_parameters("access", "secret", None).unwrap();
+        assert!(S3Client::try_new(provider, endpoint).is_err());
+    }
+
+    #[test]
+    fn object_body_is_written_to_writer() {
+        let mut ok = response(200, Some(("Content-Length", "5")));
+        ok.body = b"hello".to_vec();
+        let mut truncated = response(206, Some(("Content-Length", "10")));
+        truncated.body = b"hello".to_vec();
+        let client = client(vec![response(503, None), ok, truncated]);
+
+        let mut input = GetObjectRequest::default();
+        input.bucket = "bucket".to_string();
+        input.key = "key".to_string();
+        let mut body: Vec<u8> = Vec::new();
+        let mut operation = Operation::default();
+
+        let output = client.get_object_to_writer(&input, &mut body, Some(&mut operation)).unwrap();
+        assert_eq!(body, b"hello".to_vec());
+        assert_eq!(output.content_length, 5);
+        assert!(output.body.is_empty());
+        assert_eq!(operation.payload_size, 5);
+        assert_eq!(operation.retries, 1);
+
+        input.range = Some("bytes=0-9".to_string());
+        let err = client.get_object_to_writer(&input, &mut Vec::new(), None).unwrap_err();
+        assert_eq!(err.kind, S3ErrorKind::Transport);
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2017 LambdaStack All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Library Documentation
+//!
+//! Server-side encryption with customer provided keys (SSE-C). S3 encrypts the object with the
+//! key and then discards it so the same key must be sent with every request that reads the
+//! object (GET, HEAD, copy source) or writes it (PUT, multipart upload create and part, copy).
+//!
+//! ```ignore
+//! let key = try!(CustomerKey::new(&raw_key)); // 32 bytes
+//!
+//! let mut put = PutObjectRequest::default();
+//! put.sse_customer = Some(key.clone());
+//!
+//! let mut get = GetObjectRequest::default();
+//! get.sse_customer = Some(key);
+//! ```
+
+use std::fmt;
+
+use rustc_serialize::base64::{FromBase64, STANDARD, ToBase64};
+
+use aws::common::signature::{SignedRequest, content_md5};
+use aws::errors::s3::S3Error;
+
+/// The only algorithm S3 supports for customer provided keys.
+pub const SSE_CUSTOMER_ALGORITHM: &'static str = "AES256";
+
+/// A 256-bit customer provided encryption key. The base64 encoded key and key MD5 headers
+/// are computed from it when a request is sent.
+#[derive(Clone, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct CustomerKey {
+    key: Vec<u8>,
+}
+
+// Impls below...
+
+impl CustomerKey {
+    /// Creates a key from the raw 32 bytes.
+    pub fn new(key: &[u8]) -> Result<CustomerKey, S3Error> {
+        if key.len() != 32 {
+            return Err(S3Error::new(format!("SSE-C key must be 256 bits (32 bytes) and not {} bytes", key.len())));
+        }
+        Ok(CustomerKey { key: key.to_vec() })
+    }
+
+    /// Creates a key from its base64 encoding.
+    pub fn from_base64(key: &str) -> Result<CustomerKey, S3Error> {
+        match key.from_base64() {
+            Ok(key) => CustomerKey::new(&key),
+            Err(_) => Err(S3Error::new("SSE-C key is not valid base64")),
+        }
+    }
+
+    /// Value of the x-amz-server-side-encryption-customer-algorithm header.
+    pub fn algorithm(&self) -> &str {
+        SSE_CUSTOMER_ALGORITHM
+    }
+
+    /// Value of the x-amz-server-side-encryption-customer-key header.
+    pub fn key_base64(&self) -> String {
+        self.key.to_base64(STANDARD)
+    }
+
+    /// Value of the x-amz-server-side-encryption-customer-key-MD5 header.
+    pub fn key_md5(&self) -> String {
+        content_md5(&self.key)
+    }
+}
+
+// NOTE: The key is never printed so it can't end up in logs.
+impl fmt::Debug for CustomerKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CustomerKey {{ algorithm: {}, key_md5: {} }}", self.algorithm(), self.key_md5())
+    }
+}
+
+/// Adds the SSE-C headers to a request. `prefix` is `x-amz-` for the object of the request and
+/// `x-amz-copy-source-` for the source object of a copy. A `CustomerKey` takes precedence over
+/// the already encoded `algorithm`, `key` and `key_md5` values. When only an encoded key is
+/// given the algorithm defaults to AES256 and the key MD5 is computed from it.
+pub fn add_sse_customer_headers(request: &mut SignedRequest,
+                                prefix: &str,
+                                customer_key: &Option<CustomerKey>,
+                                algorithm: &Option<String>,
+                                key: &Option<String>,
+                                key_md5: &Option<String>)
+                                -> Result<(), S3Error> {
+    let (algorithm, key, key_md5) = match *customer_key {
+        Some(ref customer_key) => {
+            (customer_key.algorithm().to_string(), customer_key.key_base64(), customer_key.key_md5())
+        },
+        None => {
+            let key = match *key {
+                Some(ref key) => key.to_string(),
+                None => {
+                    if algorithm.is_some() || key_md5.is_some() {
+                        return Err(S3Error::new("SSE-C algorithm or key MD5 specified but no key provided."));
+                    }
+                    return Ok(());
+                },
+            };
+            let key_md5 = match *key_md5 {
+                Some(ref key_md5) => key_md5.to_string(),
+                None => try!(CustomerKey::from_base64(&key)).key_md5(),
+            };
+            let algorithm = match *algorithm {
+                Some(ref algorithm) => algorithm.to_string(),
+                None => SSE_CUSTOMER_ALGORITHM.to_string(),
+            };
+            (algorithm, key, key_md5)
+        },
+    };
+
+    request.add_header(&format!("{}server-side-encryption-customer-algorithm", prefix), &algorithm);
+    request.add_header(&format!("{}server-side-encryption-customer-key", prefix), &key);
+    request.add_header(&format!("{}server-side-encryption-customer-key-MD5", prefix), &key_md5);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn customer_key_encoding() {
+        let key = CustomerKey::new(&[7u8; 32]).unwrap();
+        assert_eq!(key.algorithm(), "AES256");
+        assert_eq!(CustomerKey::from_base64(&key.key_base64()).unwrap(), key);
+        assert_eq!(key.key_md5().len(), 24);
+        assert!(!format!("{:?}", key).contains(&key.key_base64()));
+        assert!(CustomerKey::new(&[7u8; 16]).is_err());
+    }
+}
@@ -325,16 +325,17 @@ impl CORSRuleParser {
         let mut obj = CORSRule::default();
         loop {
             let current_name = try!(peek_at_name(stack));
+            // NOTE: The repeated elements may be interleaved so extend instead of replace.
             if current_name == "AllowedHeader" {
-                obj.allowed_headers = try!(AllowedHeadersParser::parse_xml("AllowedHeader", stack));
+                obj.allowed_headers.extend(try!(AllowedHeadersParser::parse_xml("AllowedHeader", stack)));
                 continue;
             }
             if current_name == "ExposeHeader" {
-                obj.expose_headers = try!(ExposeHeadersParser::parse_xml("ExposeHeader", stack));
+                obj.expose_headers.extend(try!(ExposeHeadersParser::parse_xml("ExposeHeader", stack)));
                 continue;
             }
             if current_name == "AllowedMethod" {
-                obj.allowed_methods = try!(AllowedMethodsParser::parse_xml("AllowedMethod", stack));
+                obj.allowed_methods.extend(try!(AllowedMethodsParser::parse_xml("AllowedMethod", stack)));
                 continue;
             }
             if current_name == "MaxAgeSeconds" {
@@ -342,7 +343,11 @@ impl CORSRuleParser {
                 continue;
             }
             if current_name == "AllowedOrigin" {
-                obj.allowed_origins = try!(AllowedOriginsParser::parse_xml("AllowedOrigin", stack));
+                obj.allowed_origins.extend(try!(AllowedOriginsParser::parse_xml("AllowedOrigin", stack)));
+                continue;
+            }
+            if current_name == "ID" {
+                obj.id = Some(try!(IDParser::parse_xml("ID", stack)));
                 continue;
             }
             break;
@@ -364,6 +369,9 @@ impl CORSRuleWriter {
         AllowedMethodsWriter::write_params(params, &(prefix.to_string() + "AllowedMethod"), &obj.allowed_methods);
         MaxAgeSecondsWriter::write_params(params, &(prefix.to_string() + "MaxAgeSeconds"), &obj.max_age_seconds);
         AllowedOriginsWriter::write_params(params, &(prefix.to_string() + "AllowedOrigin"), &obj.allowed_origins);
+        if let Some(ref obj) = obj.id {
+            IDWriter::write_params(params, &(prefix.to_string() + "ID"), obj);
+        }
     }
 }
 
@@ -424,8 +432,8 @@ pub struct CORSRulesParser;
 impl CORSRulesParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<CORSRules, XmlParseError> {
         let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "CORSRule" {
-            obj.push(try!(CORSRuleParser::parse_xml("CORSRule", stack)));
+        while try!(peek_at_name(stack)) == tag_name {
+            obj.push(try!(CORSRuleParser::parse_xml(tag_name, stack)));
         }
         Ok(obj)
     }
@@ -1279,7 +1287,11 @@ impl LambdaFunctionConfigurationParser {
                 continue;
             }
             if current_name == "Event" {
-                obj.events = try!(EventListParser::parse_xml("Event", stack));
+                obj.events.extend(try!(EventListParser::parse_xml("Event", stack)));
+                continue;
+            }
+            if current_name == "Filter" {
+                obj.filter = Some(try!(NotificationConfigurationFilterParser::parse_xml("Filter", stack)));
                 continue;
             }
             break;
@@ -1325,8 +1337,10 @@ impl WebsiteConfigurationParser {
                 obj.error_document = try!(ErrorDocumentParser::parse_xml("ErrorDocument", stack));
                 continue;
             }
-            if current_name == "RoutingRule" {
+            if current_name == "RoutingRules" {
+                try!(start_element("RoutingRules", stack));
                 obj.routing_rules = try!(RoutingRulesParser::parse_xml("RoutingRule", stack));
+                try!(end_element("RoutingRules", stack));
                 continue;
             }
             break;
@@ -1739,8 +1753,8 @@ pub struct ReplicationRulesParser;
 impl ReplicationRulesParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ReplicationRules, XmlParseError> {
         let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "ReplicationRule" {
-            obj.push(try!(ReplicationRuleParser::parse_xml("ReplicationRule", stack)));
+        while try!(peek_at_name(stack)) == tag_name {
+            obj.push(try!(ReplicationRuleParser::parse_xml(tag_name, stack)));
         }
         Ok(obj)
     }
@@ -2251,8 +2265,8 @@ impl ReplicationConfigurationParser {
         let mut obj = ReplicationConfiguration::default();
         loop {
             let current_name = try!(peek_at_name(stack));
-            if current_name == "ReplicationRule" {
-                obj.rules = try!(ReplicationRulesParser::parse_xml("ReplicationRule", stack));
+            if current_name == "Rule" {
+                obj.rules = try!(ReplicationRulesParser::parse_xml("Rule", stack));
                 continue;
             }
             if current_name == "Role" {
@@ -2273,7 +2287,7 @@ impl ReplicationConfigurationWriter {
     pub fn write_params(params: &mut Params, name: &str, obj: &ReplicationConfiguration) {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
-        ReplicationRulesWriter::write_params(params, &(prefix.to_string() + "ReplicationRule"), &obj.rules);
+        ReplicationRulesWriter::write_params(params, &(prefix.to_string() + "Rule"), &obj.rules);
         RoleWriter::write_params(params, &(prefix.to_string() + "Role"), &obj.role);
     }
 }
@@ -2289,8 +2303,8 @@ pub struct CommonPrefixListParser;
 impl CommonPrefixListParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<CommonPrefixList, XmlParseError> {
         let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "CommonPrefix" {
-            obj.push(try!(CommonPrefixParser::parse_xml("CommonPrefix", stack)));
+        while try!(peek_at_name(stack)) == tag_name {
+            obj.push(try!(CommonPrefixParser::parse_xml(tag_name, stack)));
         }
         Ok(obj)
     }
@@ -2566,8 +2580,8 @@ pub struct EventListParser;
 impl EventListParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<EventList, XmlParseError> {
         let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "Event" {
-            obj.push(try!(EventParser::parse_xml("Event", stack)));
+        while try!(peek_at_name(stack)) == tag_name {
+            obj.push(try!(EventParser::parse_xml(tag_name, stack)));
         }
         Ok(obj)
     }
@@ -2662,6 +2676,13 @@ pub struct GetBucketWebsiteRequest {
     pub bucket: BucketName,
 }
 
+//#[derive(Debug, Default)]
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct GetBucketWebsiteOutput {
+    pub website_configuration: WebsiteConfiguration,
+    pub response_metadata: ResponseMetadata,
+}
+
 /// Parse `GetBucketWebsiteRequest` from XML
 pub struct GetBucketWebsiteRequestParser;
 
@@ -2700,8 +2721,8 @@ pub struct RulesParser;
 impl RulesParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<Rules, XmlParseError> {
         let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "Rule" {
-            obj.push(try!(RuleParser::parse_xml("Rule", stack)));
+        while try!(peek_at_name(stack)) == tag_name {
+            obj.push(try!(RuleParser::parse_xml(tag_name, stack)));
         }
         Ok(obj)
     }
@@ -2731,6 +2752,9 @@ pub struct Rule {
     pub transition: Option<Transition>,
     /// Prefix identifying one or more objects to which the rule applies.
     pub prefix: Prefix,
+    /// Selects the objects by prefix and/or tags instead of `prefix`. When set, `prefix` is not
+    /// sent since S3 does not accept both.
+    pub filter: Option<LifecycleRuleFilter>,
     pub expiration: Option<LifecycleExpiration>,
     pub noncurrent_version_transition: Option<NoncurrentVersionTransition>,
     /// Unique identifier for the rule. The value cannot be longer than 255
@@ -2738,6 +2762,45 @@ pub struct Rule {
     pub id: Option<ID>,
 }
 
+/// The objects a lifecycle `Rule` applies to. An object must match the prefix and have all of
+/// the tags.
+#[derive(Debug, Default, RustcDecodable, RustcEncodable)]
+pub struct LifecycleRuleFilter {
+    pub prefix: Prefix,
+    pub tags: TagSet,
+}
+
+/// Parse `LifecycleRuleFilter` from XML
+struct LifecycleRuleFilterParser;
+
+impl LifecycleRuleFilterParser {
+    pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<LifecycleRuleFilter, XmlParseError> {
+        try!(start_element(tag_name, stack));
+        let mut obj = LifecycleRuleFilter::default();
+        loop {
+            let current_name = try!(peek_at_name(stack));
+            if current_name == "Prefix" {
+                obj.prefix = try!(PrefixParser::parse_xml("Prefix", stack));
+                continue;
+            }
+            if current_name == "Tag" {
+                obj.tags.extend(try!(TagSetParser::parse_xml("Tag", stack)));
+                continue;
+            }
+            // NOTE: And holds the same elements when both a prefix and tags (or several tags) are used.
+            if current_name == "And" {
+                let and = try!(LifecycleRuleFilterParser::parse_xml("And", stack));
+                obj.prefix = and.prefix;
+                obj.tags.extend(and.tags);
+                continue;
+            }
+            break;
+        }
+        try!(end_element(tag_name, stack));
+        Ok(obj)
+    }
+}
+
 /// Parse `Rule` from XML
 struct RuleParser;
 
@@ -2763,6 +2826,10 @@ impl RuleParser {
                 obj.prefix = try!(PrefixParser::parse_xml("Prefix", stack));
                 continue;
             }
+            if current_name == "Filter" {
+                obj.filter = Some(try!(LifecycleRuleFilterParser::parse_xml("Filter", stack)));
+                continue;
+            }
             if current_name == "Expiration" {
                 obj.expiration = Some(try!(LifecycleExpirationParser::parse_xml("Expiration", stack)));
                 continue;
@@ -3076,14 +3143,14 @@ impl TransitionStorageClassWriter {
     }
 }
 
-pub type ContentLength = i32;
+pub type ContentLength = i64;
 /// Parse `ContentLength` from XML
 pub struct ContentLengthParser;
 
 impl ContentLengthParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ContentLength, XmlParseError> {
         try!(start_element(tag_name, stack));
-        let obj = i32::from_str(try!(characters(stack)).as_ref()).unwrap();
+        let obj = i64::from_str(try!(characters(stack)).as_ref()).unwrap();
         try!(end_element(tag_name, stack));
         Ok(obj)
     }
@@ -3433,6 +3500,7 @@ impl PutBucketRequestPaymentRequestWriter {
 pub struct ListBucketsOutput {
     pub owner: Owner,
     pub buckets: Buckets,
+    pub response_metadata: ResponseMetadata,
 }
 
 /// Parse `ListBucketsOutput` from XML
@@ -3479,6 +3547,7 @@ impl ListBucketsOutputWriter {
 #[derive(Debug, Default, RustcDecodable, RustcEncodable)]
 pub struct CreateBucketOutput {
     pub location: Location,
+    pub response_metadata: ResponseMetadata,
 }
 
 /// Parse `CreateBucketOutput` from XML
@@ -3791,7 +3860,11 @@ impl QueueConfigurationParser {
                 continue;
             }
             if current_name == "Event" {
-                obj.events = try!(EventListParser::parse_xml("Event", stack));
+                obj.events.extend(try!(EventListParser::parse_xml("Event", stack)));
+                continue;
+            }
+            if current_name == "Filter" {
+                obj.filter = Some(try!(NotificationConfigurationFilterParser::parse_xml("Filter", stack)));
                 continue;
             }
             break;
@@ -4317,8 +4390,8 @@ pub struct RoutingRulesParser;
 impl RoutingRulesParser {
     pub fn parse_xml<T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<RoutingRules, XmlParseError> {
         let mut obj = Vec::new();
-        while try!(peek_at_name(stack)) == "RoutingRule" {
-            obj.push(try!(RoutingRuleParser::parse_xml("RoutingRule", stack)));
+        while try!(peek_at_name(stack)) == tag_name {
+            obj.push(try!(RoutingRuleParser::parse_xml(tag_name, stack)));
         }
         Ok(obj)
     }
@@ -4419,6 +4492,10 @@ impl DestinationParser {
                 obj.bucket = try!(BucketNameParser::parse_xml("Bucket", stack));
                 continue;
             }
+            if current_name == "StorageClass" {
+                obj.storage_class = Some(try!(StorageClassParser::parse_xml("StorageClass", stack)));
+                continue;
+            }
             break;
         }
         try!(end_element(tag_name, stack));
@@ -4434,6 +4511,9 @@ impl DestinationWriter {
         let mut prefix = name.to_string();
         if prefix != "" { prefix.push_str("."); }
         BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
+        if let Some(ref obj) = obj.storage_class {
+            StorageClassWriter::write_params(params, &(prefix.to_string() + "StorageClass"), obj);
+        }
     }
 }
 
@@ -28,16 +28,61 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::{Read, Write};
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
 
 use httparse;
 use hyper;
 use hyper::method::Method;
 use hyper::version::HttpVersion;
-use hyper::net::{HttpConnector, HttpsStream, NetworkConnector, SslClient};
+use hyper::net::{HttpConnector, HttpStream, HttpsStream, NetworkConnector, SslClient};
 
 use http::client::proxy::Proxy;
 
+/// A connector like hyper's `HttpConnector` that gives up connecting after a timeout instead of
+/// waiting for the OS (which can take minutes when a host drops the packets). Every address the
+/// host resolves to is tried with the full timeout.
+#[derive(Debug, Clone, Default)]
+pub struct TimeoutConnector {
+    timeout: Option<Duration>,
+}
+
+impl TimeoutConnector {
+    /// Creates a new connector. `None` waits as long as the OS does.
+    pub fn new(timeout: Option<Duration>) -> Self {
+        TimeoutConnector { timeout: timeout }
+    }
+}
+
+impl NetworkConnector for TimeoutConnector {
+    type Stream = HttpStream;
+
+    fn connect(&self, host: &str, port: u16, scheme: &str) -> hyper::Result<HttpStream> {
+        if scheme != "http" {
+            return Err(hyper::Error::from(io::Error::new(io::ErrorKind::InvalidInput,
+                                                         "Invalid scheme for Http")));
+        }
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return Ok(HttpStream(try!(TcpStream::connect((host, port))))),
+        };
+
+        let mut last_err = io::Error::new(io::ErrorKind::InvalidInput,
+                                          format!("Could not resolve {}", host));
+        for addr in try!((host, port).to_socket_addrs()) {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(stream) => return Ok(HttpStream(stream)),
+                Err(err) => {
+                    debug!("Connecting to {} failed: {}", addr, err);
+                    last_err = err;
+                },
+            }
+        }
+        Err(hyper::Error::from(last_err))
+    }
+}
+
 /// A connector that uses an HTTP proxy server (pass-through for plaintext and tunneled for SSL
 /// sessions).
 pub struct ProxyHttpsConnector<S: SslClient> {